@@ -0,0 +1,62 @@
+//! Runtime API definition for the quiz pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait QuizApi<AccountId, BlockNumber, Hash, Solution, QuizSummary, AttemptStatus, QuizTerms, Difficulty, Quiz> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+		Solution: Codec,
+		QuizSummary: Codec,
+		AttemptStatus: Codec,
+		QuizTerms: Codec,
+		Difficulty: Codec,
+		Quiz: Codec,
+	{
+		/// The block in which the quiz will be deleted, `None` if it does not exist.
+		fn deletion_block(quiz_count: u64) -> Option<BlockNumber>;
+
+		/// The ids of the quizzes scheduled to be deleted in `block`.
+		fn scheduled_deletions(block: BlockNumber) -> Vec<Hash>;
+
+		/// The number of submitted attempts of the quiz.
+		fn attempt_count(quiz_count: u64) -> u32;
+
+		/// The answer key of an expired quiz, `None` while the quiz is active or if it is unknown.
+		fn reveal_solution(quiz_count: u64) -> Option<Solution>;
+
+		/// The option order of every question as shown to `who`, display position `i` holds option
+		/// `order[i]`.
+		fn option_order(quiz_count: u64, who: AccountId) -> Option<Vec<[u8; 4]>>;
+
+		/// A page of quizzes in creation order after `start_after`, holding at most `limit` quizzes,
+		/// and the count to pass as `start_after` for the next page, `None` after the last page.
+		/// Only a bounded number of counts is walked per call, so a page may be short or empty
+		/// while more pages follow.
+		fn list_quizzes(start_after: Option<u64>, limit: u32) -> (Vec<(u64, QuizSummary)>, Option<u64>);
+
+		/// The answer explanations of a quiz, `None` unless `who` has attempted it.
+		fn explanations(quiz_count: u64, who: AccountId) -> Option<Vec<Vec<u8>>>;
+
+		/// The score `who` would get for the submission made through `referrer`, or why the attempt
+		/// would be rejected. Nothing is written or transferred.
+		fn dry_run_attempt(quiz_count: u64, who: AccountId, submission: Solution, referrer: Option<AccountId>) -> Result<u8, AttemptStatus>;
+
+		/// The costs, fee and stake of attempting the quiz, `None` if it does not exist.
+		fn quiz_terms(quiz_count: u64) -> Option<QuizTerms>;
+
+		/// The 1-based rank of `who` by rating, `None` if they have never been rated.
+		fn user_rank(who: AccountId) -> Option<u32>;
+
+		/// The share of answers players got wrong over all attempts, `None` before the first one.
+		fn observed_difficulty(quiz_count: u64) -> Option<Difficulty>;
+
+		/// The quizzes with the given counts in the order asked for, `None` for missing ones. At most
+		/// the first 100 counts are looked up.
+		fn get_quizzes(quiz_counts: Vec<u64>) -> Vec<Option<Quiz>>;
+	}
+}
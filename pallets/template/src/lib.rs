@@ -2,14 +2,33 @@
 
 pub use pallet::*;
 
+mod rating;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use sp_std::vec::Vec;
+	use crate::rating;
 	use frame_system::pallet_prelude::*;
 	use frame_support::pallet_prelude::*;
 	use frame_support::{
-		sp_runtime::traits::{Hash, AccountIdConversion, SaturatedConversion},
-		traits::{Currency, ExistenceRequirement},
+		dispatch::{DispatchErrorWithPostInfo, WithPostDispatchInfo},
+		log,
+		storage,
+		transactional,
+		StorageHasher,
+		PalletId,
+		sp_runtime::{
+			traits::{Hash, AccountIdConversion, SaturatedConversion, Saturating, Zero},
+			Perbill,
+		},
+		sp_io::KillStorageResult,
+		traits::{BalanceStatus, Currency, ExistenceRequirement, OnUnbalanced, PalletInfoAccess, ReservableCurrency, VestingSchedule, WithdrawReasons},
 	};
 
 	#[cfg(feature = "std")]
@@ -17,15 +36,32 @@ pub mod pallet {
 
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
 	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type DeletionBucketOf<T> = BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxDeletionBucketLen>;
+	type ExplanationOf<T> = BoundedVec<u8, <T as Config>::MaxTextLen>;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+	// two letter ISO 639-1 language code, e.g. `*b"en"`
+	pub type LanguageCode = [u8; 2];
 
 
 	//Struct for Quiz
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Quiz<T:Config>{
+		pub count: u64,
 		pub owner: AccountOf<T>,
 		pub questions: Vec<Question>,
+		pub lang: LanguageCode,
 		pub rating: u8,
+		pub deposit: BalanceOf<T>,
+		pub expires_at: T::BlockNumber,
+		// pass/fail stake replacing the per wrong answer charge when not zero
+		pub stake: BalanceOf<T>,
+		pub pass_threshold: u8,
+		// bumped on every edit so recorded results stay tied to the content they were scored on
+		pub version: u32,
+		// attempts each player gets at the quiz, zero for unlimited
+		pub max_attempts_per_user: u32,
 	}
 
 	//Struct for Question
@@ -37,32 +73,613 @@ pub mod pallet {
 		pub option2: Vec<u8>,
 		pub option3: Vec<u8>,
 		pub option4: Vec<u8>,
+		pub lang: Option<LanguageCode>,
+		// options are ordered values (e.g. numeric ranges) and a neighbouring option is a near miss
+		pub ordinal: bool,
+		// several options are correct and all of them have to be picked, answered with `Answer::Several`
+		pub multi_select: bool,
+	}
+
+	// the target of every log line of the pallet
+	pub const LOG_TARGET: &str = "runtime::quiz";
+
+	// computation charged on top of the storage accesses of every call until the pallet is benchmarked
+	pub const BASE_WEIGHT: Weight = 10_000;
+	// computation per question handled by a call, e.g. validating its language
+	pub const PER_QUESTION_WEIGHT: Weight = 1_000;
+
+	// number of options every question offers
+	pub const OPTIONS_PER_QUESTION: u8 = 4;
+	// number of buckets the quizzes expiring in one block are spread over
+	pub const DELETION_BUCKETS: u8 = 8;
+	// most blocks after its expiry a quiz spills into when the buckets of that block are full
+	pub const DELETION_SPILL_BLOCKS: u32 = 4;
+	// largest page `list_quizzes` returns
+	pub const MAX_QUIZ_PAGE: u32 = 100;
+	// most quiz counts `list_quizzes` walks in one call, deleted ones included
+	pub const MAX_QUIZ_SCAN: u32 = 1_000;
+	// most quizzes `get_quizzes` looks up in one call
+	pub const MAX_QUIZ_LOOKUP: u32 = 100;
+	// highest quality rating a player can give a quiz
+	pub const MAX_STARS: u8 = 5;
+	// number of questions every quiz consists of
+	pub const QUESTIONS_PER_QUIZ: usize = 5;
+	// number of players kept on the leaderboard
+	pub const LEADERBOARD_SIZE: usize = 10;
+	// number of latest scores kept per player to recompute their rating from
+	pub const MAX_SCORE_HISTORY: usize = 50;
+	// most distinct accounts funding the prize pool of one quiz, each is refunded separately
+	pub const MAX_PRIZE_FUNDERS: u32 = 16;
+	// most per-player entries of removed quizzes cleared in one block
+	pub const MAX_CLEANUP_PER_BLOCK: u32 = 500;
+
+	// function ids of `extension_call`, the entry point a runtime's contracts chain extension
+	// forwards to after charging `extension_weight`. Input and output are SCALE encoded:
+	// - `get_quiz`: `u64` quiz count to `Option<QuizSummary>`
+	// - `max_score`: `u64` quiz count to `Option<u8>`
+	// - `attempt`: `(u64, Solution)` to the `AttemptReceipt` of an attempt by the calling contract,
+	//   only while `T::AllowContractAttempts` is on
+	pub const EXTENSION_GET_QUIZ: u32 = 1;
+	pub const EXTENSION_MAX_SCORE: u32 = 2;
+	pub const EXTENSION_ATTEMPT: u32 = 3;
+
+	// the option picked for a question. Only the four options and abstaining can be represented,
+	// so an answer key or submission can never point at an option that does not exist. This
+	// replaces the `T::AnswerIndexBase` setting and its `to_option_index`/`from_option_index`
+	// conversions: clients name the option instead of counting it, so there is no base left to
+	// configure. Clients that counted options from 1 map `n` to `Answer::new(n - 1)`, and every
+	// index the pallet hands out, like `index` and the option orders, is 0-based
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum Answer {
+		Option1,
+		Option2,
+		Option3,
+		Option4,
+		Abstain,
+		// the options picked on a multi-select question, bit `i` for the option at index `i`
+		Several(u8),
+	}
+
+	impl Answer {
+		// the answer picking the option at the 0-based `index`, `None` if there is no such option
+		pub fn new(index: u8) -> Option<Self> {
+			match index {
+				0 => Some(Answer::Option1),
+				1 => Some(Answer::Option2),
+				2 => Some(Answer::Option3),
+				3 => Some(Answer::Option4),
+				_ => None,
+			}
+		}
+
+		// the 0-based index of the picked option, `None` when abstaining
+		pub fn index(self) -> Option<u8> {
+			match self {
+				Answer::Option1 => Some(0),
+				Answer::Option2 => Some(1),
+				Answer::Option3 => Some(2),
+				Answer::Option4 => Some(3),
+				Answer::Abstain => None,
+				Answer::Several(_) => None,
+			}
+		}
+
+		// the picked options as a set, bit `i` for the option at index `i`, empty when abstaining.
+		// Bits past the last option are dropped
+		pub fn options(self) -> u8 {
+			match self {
+				Answer::Several(options) => options & ((1 << OPTIONS_PER_QUESTION) - 1),
+				answer => answer.index().map_or(0, |index| 1 << index),
+			}
+		}
+
+		// whether the answer picks the correct options and only those
+		pub fn matches(self, correct: Answer) -> bool {
+			self.options() != 0 && self.options() == correct.options()
+		}
 	}
 
 	//Struct for Solution of a quiz --- a quiz is consist of 5 questions so the the solution will have 5 answers
-	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	// `Quiz` and `Question` hold unbounded `Vec`s and cannot be bounded until those become `BoundedVec`s
+	#[derive(Clone, Encode, Decode, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Solution{
-		pub answer1: u8,
-		pub answer2: u8,
-		pub answer3: u8,
-		pub answer4: u8,
-		pub answer5: u8,
+		pub answer1: Answer,
+		pub answer2: Answer,
+		pub answer3: Answer,
+		pub answer4: Answer,
+		pub answer5: Answer,
+	}
+
+	impl Solution {
+		// the answers in question order
+		pub fn answers(&self) -> [Answer; QUESTIONS_PER_QUIZ] {
+			[self.answer1, self.answer2, self.answer3, self.answer4, self.answer5]
+		}
+
+		// the number of answers, which has to match the number of questions answered
+		pub fn answer_count(&self) -> usize {
+			self.answers().len()
+		}
+
+		// builds a solution from answers in question order
+		pub fn from_answers(answers: [Answer; QUESTIONS_PER_QUIZ]) -> Self {
+			Solution {
+				answer1: answers[0],
+				answer2: answers[1],
+				answer3: answers[2],
+				answer4: answers[3],
+				answer5: answers[4],
+			}
+		}
+	}
+
+	//Struct for the outcome of an attempt handed out by `attempt_quiz_with_receipt`
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct AttemptReceipt<Balance>{
+		pub score: u8,
+		pub max_score: u8,
+		// everything the attempt cost the player, fee included
+		pub charged: Balance,
+		pub new_rating: u8,
+	}
+
+	//Struct for the best score of an account on a quiz, which ranks it in the tournament
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct BestScore<T:Config>{
+		// in hundredths of a point, so partial credit breaks ties
+		pub score: u32,
+		// the block the score was first reached, earlier ranks higher on a tie
+		pub achieved_at: T::BlockNumber,
+	}
+
+	//Struct for the latest result of an account on a quiz
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct AttemptResult<T:Config>{
+		pub score: u8,
+		pub correct: u8,
+		pub version: u32,
+		pub attempted_at: T::BlockNumber,
+		// blocks between the start of the attempt and its submission, `None` if it was not started
+		pub elapsed: Option<T::BlockNumber>,
+	}
+
+	//Struct for the overview of a quiz shown when browsing, without questions or answers
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct QuizSummary<T:Config>{
+		pub owner: AccountOf<T>,
+		pub rating: u8,
+		pub lang: LanguageCode,
+		pub version: u32,
+		pub attempts: u32,
+		pub expires_at: T::BlockNumber,
+	}
+
+	//Struct for everything an attempt of a quiz can cost, as enforced by `attempt_quiz`
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct QuizTerms<T:Config>{
+		pub cost_per_wrong_answer: BalanceOf<T>,
+		// applied to the per wrong answer charge of a stretch attempt
+		pub stretch_multiplier: u32,
+		pub attempt_fee: BalanceOf<T>,
+		// replaces the per wrong answer charge when not zero
+		pub stake: BalanceOf<T>,
+		pub pass_threshold: u8,
+		// the most a player at the quiz's rating can be charged, fee included
+		pub max_charge: BalanceOf<T>,
+		// split among the top scorers once the quiz closes
+		pub prize_pool: BalanceOf<T>,
+	}
+
+	//Struct for a player on the leaderboard
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct LeaderboardEntry<T:Config>{
+		pub account: AccountOf<T>,
+		pub rating: u8,
+		// the block the player first reached this rating, earlier ranks higher on a tie
+		pub achieved_at: T::BlockNumber,
+	}
+
+	//Struct for the player given quality rating of a quiz
+	#[derive(Clone, Encode, Decode, PartialEq, Default, RuntimeDebug, TypeInfo)]
+	pub struct QuizQualityRating{
+		pub sum: u32,
+		pub count: u32,
+	}
+
+	// answers given to a quiz over all its attempts, the questions asked depend on the draw
+	#[derive(Clone, Encode, Decode, PartialEq, Default, RuntimeDebug, TypeInfo)]
+	pub struct AnswerTotals{
+		pub correct: u64,
+		pub asked: u64,
+	}
+
+	// why an attempt would be rejected, as reported by `dry_run_attempt`
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum AttemptStatus {
+		QuizDoesNotExist,
+		OwnerCannotAttemptQuiz,
+		LinkedAccountBlocked,
+		UserRatingTooLow,
+		AnswerCountMismatch,
+		InsufficientBalance,
+		MonetizationOptedOut,
+		CooldownActive,
+		AlreadyAttemptedSource,
+		AttemptLimitReached,
+		DailyQuotaExceeded,
+		WouldReapAccount,
+		AccountBanned,
+		QuizExpiring,
+		SelfReferral,
+		Other,
+	}
+
+	impl<T: Config> From<Error<T>> for AttemptStatus {
+		fn from(error: Error<T>) -> Self {
+			match error {
+				Error::<T>::QuizDoesNotExist => AttemptStatus::QuizDoesNotExist,
+				Error::<T>::OwnerCannotAttemptQuiz => AttemptStatus::OwnerCannotAttemptQuiz,
+				Error::<T>::LinkedAccountBlocked => AttemptStatus::LinkedAccountBlocked,
+				Error::<T>::UserRatingTooLow => AttemptStatus::UserRatingTooLow,
+				Error::<T>::AnswerCountMismatch => AttemptStatus::AnswerCountMismatch,
+				Error::<T>::InsufficientBalance => AttemptStatus::InsufficientBalance,
+				Error::<T>::MonetizationOptedOut => AttemptStatus::MonetizationOptedOut,
+				Error::<T>::CooldownActive => AttemptStatus::CooldownActive,
+				Error::<T>::AlreadyAttemptedSource => AttemptStatus::AlreadyAttemptedSource,
+				Error::<T>::AttemptLimitReached => AttemptStatus::AttemptLimitReached,
+				Error::<T>::DailyQuotaExceeded => AttemptStatus::DailyQuotaExceeded,
+				Error::<T>::WouldReapAccount => AttemptStatus::WouldReapAccount,
+				Error::<T>::AccountBanned => AttemptStatus::AccountBanned,
+				Error::<T>::QuizExpiring => AttemptStatus::QuizExpiring,
+				Error::<T>::SelfReferral => AttemptStatus::SelfReferral,
+				_ => AttemptStatus::Other,
+			}
+		}
+	}
+
+	// how fractional token amounts are rounded to whole units of the balance
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum CostRounding {
+		Down,
+		Up,
+		Nearest,
+	}
+
+	// lets other pallets, e.g. achievements, react to a completed attempt without this pallet
+	// depending on them
+	pub trait OnQuizCompleted<AccountId> {
+		fn on_completed(who: &AccountId, quiz_count: u64, score: u8);
+	}
+
+	impl<AccountId> OnQuizCompleted<AccountId> for () {
+		fn on_completed(_who: &AccountId, _quiz_count: u64, _score: u8) {}
+	}
+
+	// lets a runtime gate new and edited quizzes on a content verdict, e.g. of a moderation
+	// committee or an oracle
+	pub trait OnQuizProposed<AccountId> {
+		fn approve(owner: &AccountId, questions: &[Question]) -> bool;
+	}
+
+	impl<AccountId> OnQuizProposed<AccountId> for () {
+		fn approve(_owner: &AccountId, _questions: &[Question]) -> bool {
+			true
+		}
+	}
+
+	// lets a runtime reward a perfect score, e.g. by minting a certificate NFT
+	pub trait OnPerfectScore<AccountId> {
+		fn on_perfect_score(who: &AccountId, quiz_count: u64);
+	}
+
+	impl<AccountId> OnPerfectScore<AccountId> for () {
+		fn on_perfect_score(_who: &AccountId, _quiz_count: u64) {}
+	}
+
+	// a `VestingSchedule` refusing every schedule for runtimes without a vesting pallet, prizes
+	// are then always paid liquid
+	pub struct NoVesting<Currency, Moment>(PhantomData<(Currency, Moment)>);
+
+	impl<AccountId, C: Currency<AccountId>, Moment> VestingSchedule<AccountId> for NoVesting<C, Moment> {
+		type Moment = Moment;
+		type Currency = C;
+
+		fn vesting_balance(_who: &AccountId) -> Option<C::Balance> {
+			None
+		}
+
+		fn add_vesting_schedule(
+			_who: &AccountId,
+			_locked: C::Balance,
+			_per_block: C::Balance,
+			_starting_block: Moment,
+		) -> DispatchResult {
+			Err(DispatchError::Other("vesting is not supported"))
+		}
+
+		fn can_add_vesting_schedule(
+			_who: &AccountId,
+			_locked: C::Balance,
+			_per_block: C::Balance,
+			_starting_block: Moment,
+		) -> DispatchResult {
+			Err(DispatchError::Other("vesting is not supported"))
+		}
+
+		fn remove_vesting_schedule(
+			_who: &AccountId,
+			_schedule_index: u32,
+		) -> DispatchResult {
+			Err(DispatchError::Other("vesting is not supported"))
+		}
+	}
+
+	//Struct for a whole quiz built off-chain and submitted SCALE encoded to `add_quiz_encoded`
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct QuizPayload{
+		pub questions: Vec<Question>,
+		pub lang: LanguageCode,
+		pub solution: Solution,
+		pub rating: u8,
+	}
+
+	// one quiz of an `add_quizzes` batch
+	pub type QuizInput = QuizPayload;
+
+	//Struct for an attempt that has been started but not yet submitted
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct AttemptStart<T:Config>{
+		pub started_at: T::BlockNumber,
+		pub reserved: BalanceOf<T>,
+		// seed of the option shuffle and question draw, fixed when the attempt starts
+		pub seed: T::Hash,
 	}
 
+	// 1: `Quizzes` is a counted map and `ActiveQuizzes` is gone
+	// 2: ratings are kept in hundredths of a point
+	// 3: best scores are kept in hundredths of a point
+	// 4: earnings locks are merged into the bounded per owner `EarningsLocks`
+	// 5: the buckets of `QuizToDelete` hold at most `T::MaxDeletionBucketLen` quizzes
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
 	#[pallet::config]
     pub trait Config: frame_system::Config {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		// the tokens to deduct per wrong answer, counted in `TokenUnit`s
+		#[pallet::constant]
+		type TokensPerQuestion: Get<BalanceOf<Self>>;
+
+		// the smallest units making up one of the tokens `TokensPerQuestion` is counted in, e.g.
+		// `10^decimals` to count it in whole tokens
+		#[pallet::constant]
+		type TokenUnit: Get<BalanceOf<Self>>;
+
+		// the base amount reserved from the creator for every quiz, refunded on deletion
+		#[pallet::constant]
+		type QuizDeposit: Get<BalanceOf<Self>>;
+
+		// reserved on top of `QuizDeposit` per byte of the encoded questions
+		#[pallet::constant]
+		type QuizDepositPerByte: Get<BalanceOf<Self>>;
+
+		// the number of reports after which the creator's deposit is slashed
+		#[pallet::constant]
+		type SlashReportThreshold: Get<u32>;
+
+		// where slashed deposits go, e.g. the treasury
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		// the maximum length in bytes of a single text, e.g. an answer explanation
+		#[pallet::constant]
+		type MaxTextLen: Get<u32>;
+
+		// the minimum length in bytes of a question statement and of each of its options
+		#[pallet::constant]
+		type MinTextLen: Get<u32>;
+
+		// the highest rating a quiz can be created with
+		#[pallet::constant]
+		type MaxRating: Get<u8>;
+
+		// how far below a quiz's requirement a player may still attempt it as a stretch
+		#[pallet::constant]
+		type StretchMargin: Get<u8>;
+
+		// the factor applied to the token cost of a stretch attempt
+		#[pallet::constant]
+		type StretchMultiplier: Get<u32>;
+
+		// the extra fraction of the per wrong answer cost charged per point of a quiz's rating
+		#[pallet::constant]
+		type RatingCostFactor: Get<Perbill>;
+
+		// the rounding applied whenever a token cost is scaled by a fraction
+		#[pallet::constant]
+		type TokenCostRounding: Get<CostRounding>;
+
+		// the fraction of a point awarded for picking an option next to the correct one on an
+		// ordinal question
+		#[pallet::constant]
+		type OrdinalPartialCredit: Get<Perbill>;
+
+		// the fraction of a point awarded for picking some but not all correct options of a
+		// multi-select question and nothing else, zero to only credit exact matches
+		#[pallet::constant]
+		type MultiSelectPartialCredit: Get<Perbill>;
+
+		// the fraction of a point deducted per wrong answer, unanswered questions are not penalized
+		#[pallet::constant]
+		type WrongAnswerPenalty: Get<Perbill>;
+
+		// the origin allowed to moderate quizzes, e.g. root or a council
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		// the origin allowed to publish quizzes, resolving to the owner of the new quiz
+		type CreateOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		// a flat fee charged on every attempt regardless of the score
+		#[pallet::constant]
+		type AttemptFee: Get<BalanceOf<Self>>;
+
+		// where attempt and rating reset fees go, e.g. the treasury
+		type AttemptFeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		// whether every player is shown the options of each question in their own order
+		#[pallet::constant]
+		type ShuffleOptions: Get<bool>;
+
+		// the maximum number of quizzes that can be active at the same time
+		#[pallet::constant]
+		type MaxActiveQuizzes: Get<u32>;
+
+		// the maximum number of quizzes removed by a single `delete_all_my_quizzes` call
+		#[pallet::constant]
+		type MaxBulkDelete: Get<u32>;
+
+		// the maximum number of quizzes created by a single `add_quizzes` call
+		#[pallet::constant]
+		type MaxQuizBatch: Get<u32>;
+
+		// the maximum number of players refunded by a single call after a defective quiz was deleted
+		#[pallet::constant]
+		type MaxRefundsPerCall: Get<u32>;
+
+		// the number of blocks a quiz owner's earnings stay reserved before they can be claimed
+		#[pallet::constant]
+		type EarningsLockPeriod: Get<Self::BlockNumber>;
+
+		// the fraction of the per wrong answer charge paid to the referrer of an attempt
+		#[pallet::constant]
+		type ReferralCut: Get<Perbill>;
+
+		// the id of the account holding the prize pools
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		// the number of top scorers splitting a quiz's prize pool
+		#[pallet::constant]
+		type TournamentWinners: Get<u32>;
+
+		// the number of blocks a player has to wait after an attempt before the next one
+		#[pallet::constant]
+		type AttemptCooldown: Get<Self::BlockNumber>;
+
+		// whether an attempt that raises the player's rating clears their cooldown
+		#[pallet::constant]
+		type ResetCooldownOnImprovement: Get<bool>;
+
+		// the number of completed attempts before a player's rating counts for eligibility
+		#[pallet::constant]
+		type MinAttemptsForRating: Get<u32>;
+
+		// the rating interval at which players are congratulated, 0 to never emit milestones
+		#[pallet::constant]
+		type RatingMilestoneStep: Get<u8>;
+
+		// the weight of a new score in a player's rating, which moves this fraction of the way
+		// from the old rating towards the score
+		#[pallet::constant]
+		type RatingAlpha: Get<Perbill>;
+
+		// the number of poor attempts in a row after which a player is locked out of a quiz, 0 to
+		// never lock anyone out
+		#[pallet::constant]
+		type MaxWrongAttempts: Get<u32>;
+
+		// scores below this count as a poor attempt towards `MaxWrongAttempts`
+		#[pallet::constant]
+		type StrikeScoreThreshold: Get<u8>;
+
+		// whether answer keys picking the same option for every question are rejected
+		#[pallet::constant]
+		type RejectUniformSolutions: Get<bool>;
+
+		// the most quizzes one bucket of a block's deletion schedule holds, the quizzes expiring
+		// after it is full go into another bucket or spill into a later block
+		#[pallet::constant]
+		type MaxDeletionBucketLen: Get<u32>;
+
+		// the most weight `on_initialize` spends on expiring quizzes, the rest is deferred
+		#[pallet::constant]
+		type ExpiryWeightBudget: Get<Weight>;
+
+		// the blocks a player who started an attempt before the quiz expired has left to submit it,
+		// the quiz is kept until then. 0 removes quizzes on time regardless
+		#[pallet::constant]
+		type ExpiryGracePeriod: Get<Self::BlockNumber>;
+
+		// the most players that may have a started attempt of the same quiz at once, 0 for no limit
+		#[pallet::constant]
+		type MaxConcurrentAttempts: Get<u32>;
+
+		// whether contracts may attempt quizzes through `extension_call`, reading is always allowed
+		#[pallet::constant]
+		type AllowContractAttempts: Get<bool>;
+
+		// whether expired quizzes are kept in `ArchivedQuizzes` for review instead of being purged
+		#[pallet::constant]
+		type ArchiveExpiredQuizzes: Get<bool>;
+
+		// the fraction of the score added to the score a rating moves towards when a started attempt
+		// is submitted in the block it started in
+		#[pallet::constant]
+		type SpeedBonus: Get<Perbill>;
+
+		// the blocks over which the speed bonus decays linearly to nothing, 0 for no bonus
+		#[pallet::constant]
+		type SpeedBonusWindow: Get<Self::BlockNumber>;
 
-		// the amount of tokens to deduct per wrong answer
+		// the fee a player pays to reset their own rating, handed to `AttemptFeeDestination`
 		#[pallet::constant]
-		type TokensPerQuestion: Get<u32>;
+		type RatingResetFee: Get<BalanceOf<Self>>;
+
+		// the blocks a player has to wait between two resets of their rating
+		#[pallet::constant]
+		type RatingResetCooldown: Get<Self::BlockNumber>;
+
+		// the attempts a player may submit across all quizzes per `BlocksPerDay`, 0 for no quota
+		#[pallet::constant]
+		type DailyAttemptQuota: Get<u32>;
+
+		// the length in blocks of the window `DailyAttemptQuota` applies to
+		#[pallet::constant]
+		type BlocksPerDay: Get<Self::BlockNumber>;
+
+		// prize payouts above this amount are locked in a vesting schedule instead of paid liquid
+		#[pallet::constant]
+		type VestingThreshold: Get<BalanceOf<Self>>;
+
+		// the number of blocks a vested prize unlocks over
+		#[pallet::constant]
+		type VestingPeriod: Get<Self::BlockNumber>;
+
+		// called at the end of every scored attempt, `()` when nothing needs to react
+		type OnQuizCompleted: OnQuizCompleted<Self::AccountId>;
+
+		// called after `OnQuizCompleted` when every question was answered right, `()` to do nothing
+		type OnPerfectScore: OnPerfectScore<Self::AccountId>;
+
+		// asked to approve every question set stored, be it a new, edited or translated quiz or a
+		// question pool; `()` approves everything
+		type OnQuizProposed: OnQuizProposed<Self::AccountId>;
+
+		// locks prizes above `VestingThreshold`, `NoVesting` when the runtime has no vesting pallet
+		type Vesting: VestingSchedule<Self::AccountId, Moment = Self::BlockNumber, Currency = Self::Currency>;
     }
 
 	 // Errors.
@@ -84,6 +701,94 @@ pub mod pallet {
 		 CannotDeleteQuiz,
 		 /// If the player has not enough balance 
 		 InsufficientBalance,
+		 /// If the player already has a started attempt for the quiz
+		 AttemptAlreadyInProgress,
+		 /// If the player has no started attempt for the quiz
+		 NoAttemptInProgress,
+		 /// If the quiz owner tries to report their own quiz
+		 OwnerCannotReportQuiz,
+		 /// If the account has already reported the quiz
+		 AlreadyReported,
+		 /// If an encoded quiz payload is malformed
+		 DecodeFailed,
+		 /// If the solution of a quiz is requested before the quiz expired
+		 QuizStillActive,
+		 /// If a question is tagged with a language other than the one of its question set
+		 LanguageMismatch,
+		 /// If a translation does not have as many questions as the quiz, or a batched quiz not `QUESTIONS_PER_QUIZ`
+		 QuestionCountMismatch,
+		 /// If the maximum number of active quizzes has been reached
+		 TooManyActiveQuizzes,
+		 /// If a quiz is created with a rating above `T::MaxRating`
+		 RatingTooHighForQuiz,
+		 /// If a submission does not have one answer per question of the quiz
+		 AnswerCountMismatch,
+		 /// If the terms of a quiz are changed after it has been attempted
+		 QuizAlreadyAttempted,
+		 /// If the account was found to be linked to the quiz owner
+		 LinkedAccountBlocked,
+		 /// If a quiz is rated by an account that has not attempted it
+		 MustAttemptFirst,
+		 /// If a quality rating is not between 1 and `MAX_STARS`
+		 InvalidStars,
+		 /// If there are no unlocked earnings to claim
+		 NoEarningsToClaim,
+		 /// If an expired quiz has neither an archived copy nor a revealed answer key left
+		 NothingToPrune,
+		 /// If a question pool has fewer questions than are drawn per attempt
+		 PoolTooSmall,
+		 /// If a question pool draws no questions or more than `QUESTIONS_PER_QUIZ` per attempt
+		 InvalidDrawCount,
+		 /// If an account that opted out of monetized quizzes attempts one
+		 MonetizationOptedOut,
+		 /// If a quiz already exists at the id derived from the next quiz count
+		 QuizIdCollision,
+		 /// If a player names themselves as the referrer of their attempt
+		 SelfReferral,
+		 /// If a player attempts a quiz before their cooldown has passed
+		 CooldownActive,
+		 /// If a player attempts a clone of a quiz they have already attempted
+		 AlreadyAttemptedSource,
+		 /// If every answer of an answer key picks the same option while this is disallowed
+		 SolutionTooUniform,
+		 /// If a player has used up their attempts on a quiz
+		 AttemptLimitReached,
+		 /// If a batch holds more quizzes than `T::MaxQuizBatch`
+		 BatchTooLarge,
+		 /// If refunds are continued for a quiz that has none left
+		 NoRefundsPending,
+		 /// If a rating is recomputed for a player without any kept scores
+		 NoScoreHistory,
+		 /// If an attempt is started on an expired quiz that is only kept for attempts in progress
+		 QuizExpiring,
+		 /// If the quiz already has `T::MaxConcurrentAttempts` attempts in progress
+		 QuizBusy,
+		 /// If a chain extension call uses an unknown function id
+		 UnknownExtensionFunction,
+		 /// If a contract attempts a quiz while `T::AllowContractAttempts` is off
+		 ContractAttemptsDisabled,
+		 /// If an answer key does not have one answer per question
+		 SolutionShapeMismatch,
+		 /// If a player resets their rating again within `T::RatingResetCooldown`
+		 RatingResetTooSoon,
+		 /// If a question statement or option is shorter than `T::MinTextLen`
+		 TextTooShort,
+		 /// If `T::OnQuizProposed` rejected the content of a quiz
+		 ContentRejected,
+		 /// If a player has used up `T::DailyAttemptQuota` in the current window
+		 DailyQuotaExceeded,
+		 /// If paying for an attempt would leave the player below the existential deposit
+		 WouldReapAccount,
+		 /// If governance banned the account from creating and attempting quizzes
+		 AccountBanned,
+		 /// If a prize pool already has `MAX_PRIZE_FUNDERS` funders
+		 TooManyFunders,
+		 /// If the account has no prize left to claim
+		 NoPrizeToClaim,
+		 /// If a quiz is reported by an account that has not attempted it
+		 ReporterNotVerified,
+		 /// If every bucket of the `DELETION_SPILL_BLOCKS` blocks a quiz could expire in is full
+		 DeletionScheduleFull,
 	 }
  
 	 #[pallet::event]
@@ -91,214 +796,2987 @@ pub mod pallet {
 	 pub enum Event<T: Config> {
 		 /// A new quiz was created. \[QuizId, Account, Rating\]
 		 QuizCreated(u64, T::AccountId, u8),
-		 /// Score was generated after attempting the quiz. \[QuizID, AccountId, Score\]
-		 QuizScore(u64, T::AccountId, u8),
-		 /// Quiz was deleted in block number. \[BlockNumber\]
-		 QuizDeleted(u64),
+		 /// A quiz was copied into a new quiz. \[SourceQuizID, NewQuizID, Owner\]
+		 QuizCloned(u64, u64, T::AccountId),
+		 /// Score was generated after attempting the quiz. \[QuizID, AccountId, Score, Correct, Attempts\]
+		 QuizScore(u64, T::AccountId, u8, u8, u32),
+		 /// Quiz was deleted by its owner. \[QuizID, Deleter, BlockNumber\]
+		 QuizDeleted(u64, T::AccountId, T::BlockNumber),
+		 /// Quiz reached the end of its lifetime and was removed automatically. \[QuizID, BlockNumber\]
+		 QuizExpired(u64, T::BlockNumber),
+		 /// Quiz was expired early by governance. \[QuizID\]
+		 QuizForceExpired(u64),
+		 /// A player was paid back what the owner received from them for a defective quiz. \[QuizID, AccountId, Amount\]
+		 AttemptRefunded(u64, T::AccountId, BalanceOf<T>),
+		 /// Every player of a defective quiz has been refunded. \[QuizID\]
+		 RefundsCompleted(u64),
+		 /// An attempt was started and the worst case cost reserved. \[QuizID, AccountId\]
+		 AttemptStarted(u64, T::AccountId),
+		 /// A started attempt was cancelled and its reserve released. \[QuizID, AccountId\]
+		 AttemptCancelled(u64, T::AccountId),
+		 /// A quiz was reported as faulty. \[QuizID, AccountId, Reports\]
+		 QuizReported(u64, T::AccountId, u32),
+		 /// The creator's deposit was slashed after too many reports. \[QuizID, Owner, Amount\]
+		 CreatorSlashed(u64, T::AccountId, BalanceOf<T>),
+		 /// The questions and answer key of a quiz were edited. \[QuizID, Version\]
+		 QuizEdited(u64, u32),
+		 /// A player rated the quality of a quiz. \[QuizID, AccountId, Stars\]
+		 QuizRated(u64, T::AccountId, u8),
+		 /// The answer key of a quiz was corrected by its owner. \[QuizID\]
+		 SolutionUpdated(u64),
+		 /// A question pool was set for a quiz. \[QuizID, PoolSize, QuestionsDrawn\]
+		 QuestionPoolSet(u64, u32, u32),
+		 /// The owner attached explanations of the correct answers to a quiz. \[QuizID\]
+		 ExplanationsSet(u64),
+		 /// The pass/fail stake of a quiz was set. \[QuizID, Stake, PassThreshold\]
+		 QuizStakeSet(u64, BalanceOf<T>, u8),
+		 /// The owner limited the attempts per player, zero for unlimited. \[QuizID, MaxAttempts\]
+		 AttemptLimitSet(u64, u32),
+		 /// A player's stake was refunded on a pass or forfeited to the owner on a fail. \[QuizID, AccountId, Stake, Passed\]
+		 StakeSettled(u64, T::AccountId, BalanceOf<T>, bool),
+		 /// An account linked to the quiz owner was blocked from the quiz. \[QuizID, AccountId\]
+		 LinkedAccountBlocked(u64, T::AccountId),
+		 /// A previously linked account was allowed back into the quiz. \[QuizID, AccountId\]
+		 LinkedAccountUnblocked(u64, T::AccountId),
+		 /// A translation of a quiz was added. \[QuizID, Language\]
+		 QuizTranslated(u64, LanguageCode),
+		 /// Quizzes of an owner were deleted in bulk. \[Owner, Deleted, Remaining\]
+		 OwnerQuizzesDeleted(T::AccountId, u32, u32),
+		 /// Earnings of a quiz owner were locked. \[Owner, Amount, UnlockAt\]
+		 EarningsLocked(T::AccountId, BalanceOf<T>, T::BlockNumber),
+		 /// Unlocked earnings were claimed by a quiz owner. \[Owner, Amount\]
+		 EarningsClaimed(T::AccountId, BalanceOf<T>),
+		 /// An account opted in or out of monetized quizzes. \[Account, OptedOut\]
+		 MonetizationOptOutSet(T::AccountId, bool),
+		 /// Tokens were paid for an attempt. \[QuizID, Payer, Payee, Amount\]
+		 TokensCharged(u64, T::AccountId, T::AccountId, BalanceOf<T>),
+		 /// The prize pool of a quiz was topped up. \[QuizID, Funder, Amount\]
+		 PrizePoolFunded(u64, T::AccountId, BalanceOf<T>),
+		 /// The prize pool of a closed quiz was paid out. \[QuizID, Payouts\]
+		 TournamentSettled(u64, Vec<(T::AccountId, BalanceOf<T>)>),
+		 /// The receipt of an attempt. \[QuizID, AccountId, Receipt\]
+		 AttemptReceiptIssued(u64, T::AccountId, AttemptReceipt<BalanceOf<T>>),
+		 /// A player's rating reached a milestone for the first time since dropping below it. \[AccountId, Milestone\]
+		 RatingMilestone(T::AccountId, u8),
+		 /// A player's rating was recomputed from their score history. \[AccountId, Rating\]
+		 RatingRecomputed(T::AccountId, u8),
+		 /// A batch of quizzes was created. \[Owner, QuizIDs\]
+		 QuizzesCreated(T::AccountId, Vec<u64>),
+		 /// Which questions of an attempt were answered right, bit `i` standing for question `i`. \[QuizID, AccountId, CorrectMask\]
+		 AnswerFeedback(u64, T::AccountId, u8),
+		 /// A player paid to reset their rating to 0. \[AccountId, Fee\]
+		 RatingReset(T::AccountId, BalanceOf<T>),
+		 /// Governance banned an account from quizzes. \[AccountId\]
+		 AccountBanned(T::AccountId),
+		 /// Governance lifted the ban of an account. \[AccountId\]
+		 AccountUnbanned(T::AccountId),
+		 /// Governance removed the archived copy and revealed answer key of an expired quiz. \[QuizID\]
+		 ExpiredQuizPruned(u64),
+		 /// A prize was paid into a vesting schedule. \[QuizId, Account, Amount\]
+		 PrizeVested(u64, T::AccountId, BalanceOf<T>),
+		 /// The prize pool of a quiz nobody competed for went back to a funder. \[QuizId, Funder, Amount\]
+		 PrizeRefunded(u64, T::AccountId, BalanceOf<T>),
+		 /// A prize or refund could not be paid and is kept for the account to claim. \[QuizId, Account, Amount\]
+		 PrizeUnpaid(u64, T::AccountId, BalanceOf<T>),
+		 /// An account claimed its unpaid prizes. \[Account, Amount\]
+		 PrizeClaimed(T::AccountId, BalanceOf<T>),
 	 }
 	 
 	 #[pallet::storage]
 	 #[pallet::getter(fn get_quiz)]
-	 pub(super) type Quizzes<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Quiz<T>>; // list of quizzes
+	 pub(super) type Quizzes<T:Config> = CountedStorageMap<_, Twox64Concat, T::Hash, Quiz<T>>; // list of quizzes, counted for the active quiz cap
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_translation)]
+	 pub(super) type QuizTranslations<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, LanguageCode, Vec<Question>>; // parallel question sets per language
 
 	 #[pallet::storage]
 	 #[pallet::getter(fn get_solution)]
 	 pub(super) type Solutions<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Solution>; // list of answers
 
 	 #[pallet::storage]
-	 #[pallet::getter(fn get_user_rating)]
-	 pub(super) type UserRating<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, u8, ValueQuery>;
+	 #[pallet::getter(fn get_explanations)]
+	 pub(super) type Explanations<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Vec<ExplanationOf<T>>>; // why each answer is correct, one per question
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_archived_quiz)]
+	 pub(super) type ArchivedQuizzes<T:Config> = StorageMap<_, Twox64Concat, T::Hash, (Quiz<T>, Solution)>; // expired quizzes with their answers, kept for review
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_revealed_solution)]
+	 pub(super) type RevealedSolutions<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Solution>; // answers of expired quizzes
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_user_rating_scaled)]
+	 pub(super) type UserRating<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>; // ratings in hundredths of a point, see `rating::RATING_SCALE`
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_user_attempts)]
+	 pub(super) type UserAttempts<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>; // completed attempts per player
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_cooldown_until)]
+	 pub(super) type CooldownUntil<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, ValueQuery>; // first block a player may attempt again
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_daily_attempts)]
+	 pub(super) type DailyAttempts<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>; // the window a player last attempted in and their attempts in it
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_banned_account)]
+	 pub(super) type BannedAccounts<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, ()>; // accounts governance banned from creating and attempting quizzes
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_last_rating_reset)]
+	 pub(super) type LastRatingReset<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber>; // the block a player last reset their rating in
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_score_history)]
+	 pub(super) type ScoreHistory<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, Vec<u32>, ValueQuery>; // latest scores per player in hundredths of a point, oldest first
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_rating_count)]
+	 pub(super) type RatingCounts<T:Config> = StorageMap<_, Twox64Concat, u8, u32, ValueQuery>; // number of rated players per rating, for ranking
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_leaderboard)]
+	 pub(super) type Leaderboard<T:Config> = StorageValue<_, Vec<LeaderboardEntry<T>>, ValueQuery>; // best rated players, best first
 
 	 #[pallet::storage]
 	 #[pallet::getter(fn get_latest_quiz)]
 	 pub(super) type QuizCnt<T:Config> = StorageValue<_, u64, ValueQuery>;
 
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_quiz_by_index)]
+	 pub(super) type QuizByIndex<T:Config> = StorageMap<_, Twox64Concat, u64, T::Hash>; // id of every live quiz by its count, walked from 1 to `QuizCnt` in creation order
+
 	 #[pallet::storage]
 	 #[pallet::getter(fn get_quiz_to_delete)]
-	 pub(super) type QuizToDelete<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Vec<T::Hash>, ValueQuery>;
+	 pub(super) type QuizToDelete<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, u8, DeletionBucketOf<T>, ValueQuery>; // quizzes to delete per block and bucket, at most `T::MaxDeletionBucketLen` each
 
-	 #[pallet::hooks]
-	 impl<T:Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		 fn on_initialize(now: T::BlockNumber) -> Weight {
-			 let total_weight : Weight = 10;
-			 Self::check_and_delete_quiz(now);
-			 total_weight
-		 }
-	 }
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_next_expiry_block)]
+	 pub(super) type NextExpiryBlock<T:Config> = StorageValue<_, T::BlockNumber>; // first block whose deletion schedule may still hold quizzes, the expiries the budget left over wait there
 
-	 #[pallet::call]
-    impl<T: Config> Pallet<T> {
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_owned_quiz)]
+	 pub(super) type OwnedQuizzes<T:Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::Hash, ()>; // index of quizzes per owner
 
-		#[pallet::weight(100)]
-		pub fn add_quiz(
-			origin: OriginFor<T>,
-			question1: Question,
-			question2: Question,
-			question3: Question,
-			question4: Question,
-			question5: Question,
-			solution: Solution,
-			rating: u8,
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			ensure!(solution.answer1 > 0 && solution.answer2 > 0 && solution.answer3 > 0 && solution.answer4 > 0, <Error<T>>::InvalidOptionProvided);
-			ensure!(solution.answer1 <= 4 && solution.answer2 <= 4 && solution.answer3 <= 4 && solution.answer4 <= 4, <Error<T>>::InvalidOptionProvided);
-			let mut _questions = Vec::new();
-			_questions.push(question1);
-			_questions.push(question2);
-			_questions.push(question3);
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_owned_quiz_count)]
+	 pub(super) type OwnedQuizCount<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_attempt_count)]
+	 pub(super) type AttemptCount<T:Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>; // number of submitted attempts per quiz
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_answer_totals)]
+	 pub(super) type QuizAnswerTotals<T:Config> = StorageMap<_, Twox64Concat, T::Hash, AnswerTotals, ValueQuery>; // correct and asked answers over all attempts
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_attempt_result)]
+	 pub(super) type AttemptResults<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, AttemptResult<T>>; // latest result per quiz and account
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_quiz_quality)]
+	 pub(super) type QuizQuality<T:Config> = StorageMap<_, Twox64Concat, T::Hash, QuizQualityRating, ValueQuery>;
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_stars_given)]
+	 pub(super) type StarsGiven<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, u8>; // each player's current quality rating
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_attempt_in_progress)]
+	 pub(super) type AttemptsInProgress<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, AttemptStart<T>>; // started but not submitted attempts
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_attempts_in_progress_count)]
+	 pub(super) type AttemptsInProgressCount<T:Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>; // number of started but not submitted attempts per quiz
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_cancelled_seed)]
+	 pub(super) type CancelledSeeds<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, T::Hash>; // seed of a cancelled attempt, reused by the player's next start
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_expired_at)]
+	 pub(super) type ExpiredAt<T:Config> = StorageMap<_, Twox64Concat, T::Hash, T::BlockNumber>; // the block a quiz kept for attempts in progress originally expired in
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_linked_account)]
+	 pub(super) type LinkedAccounts<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, ()>; // accounts governance found linked to the owner
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_report)]
+	 pub(super) type QuizReports<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, ()>; // one report per account and quiz
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_report_count)]
+	 pub(super) type ReportCount<T:Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_quiz_attempts)]
+	 pub(super) type QuizAttempts<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, u32, ValueQuery>; // submitted attempts per quiz and player
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_strikes)]
+	 pub(super) type Strikes<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, u32, ValueQuery>; // poor attempts in a row
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_quiz_source)]
+	 pub(super) type QuizSources<T:Config> = StorageMap<_, Twox64Concat, T::Hash, T::Hash>; // the quiz a clone was copied from
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_question_pool)]
+	 pub(super) type QuestionPools<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Vec<(Question, Answer)>>; // questions with their answer, drawn from per attempt
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_pool_draw)]
+	 pub(super) type PoolDraws<T:Config> = StorageMap<_, Twox64Concat, T::Hash, u32>; // questions drawn from the pool per attempt, set along with `QuestionPools`
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_drawn_questions)]
+	 pub(super) type DrawnQuestions<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, Vec<u32>>; // pool indices of the latest attempt in question order
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_monetization_opt_out)]
+	 pub(super) type MonetizationOptOut<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, ()>; // accounts that never risk tokens on an attempt
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_prize_pool)]
+	 pub(super) type PrizePools<T:Config> = StorageMap<_, Twox64Concat, T::Hash, BalanceOf<T>, ValueQuery>; // held by the pallet account until the quiz closes
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_best_score)]
+	 pub(super) type BestScores<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, BestScore<T>>;
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_tournament_leaders)]
+	 pub(super) type TournamentLeaders<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Vec<(T::AccountId, BestScore<T>)>, ValueQuery>; // the `TournamentWinners` best scorers of a quiz in rank order
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_prize_funders)]
+	 pub(super) type PrizeFunders<T:Config> = StorageMap<_, Twox64Concat, T::Hash, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>; // who paid into a prize pool and how much, at most `MAX_PRIZE_FUNDERS`
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_unclaimed_prize)]
+	 pub(super) type UnclaimedPrizes<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>; // prizes and refunds the pallet account failed to pay out
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_pending_cleanup)]
+	 pub(super) type PendingCleanup<T:Config> = StorageMap<_, Twox64Concat, T::Hash, ()>; // removed quizzes whose per-player entries are still being cleared
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_earnings_locks)]
+	 pub(super) type EarningsLocks<T:Config> = StorageMap<_, Twox64Concat, T::AccountId, Vec<(T::BlockNumber, BalanceOf<T>)>, ValueQuery>; // reserved earnings of an owner by the block they unlock at, at most three entries
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_attempt_charges)]
+	 pub(super) type AttemptCharges<T:Config> = StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>; // the part of what a player paid for attempts of a quiz that went to its owner, fees and referral cuts excluded
+
+	 #[pallet::storage]
+	 #[pallet::getter(fn get_pending_refund)]
+	 pub(super) type PendingRefunds<T:Config> = StorageMap<_, Twox64Concat, T::Hash, (T::AccountId, BalanceOf<T>)>; // the owner of a deleted defective quiz and the deposit left to refund from
+
+	 #[pallet::hooks]
+	 impl<T:Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		 fn on_initialize(now: T::BlockNumber) -> Weight {
+			 let budget = T::ExpiryWeightBudget::get();
+			 let consumed = Self::check_and_delete_quiz(now, budget);
+			 // what is left of the budget clears the per-player entries of removed quizzes
+			 consumed.saturating_add(Self::continue_cleanup(budget.saturating_sub(consumed)))
+		 }
+
+		 fn on_runtime_upgrade() -> Weight {
+			 let version = StorageVersion::get::<Pallet<T>>();
+			 if version >= STORAGE_VERSION {
+				 return 0;
+			 }
+			 let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			 if version < 1 {
+				 // quizzes stored before versioning hold questions, 1-based `u8` answer keys and a
+				 // deletion schedule in layouts that no longer decode, so they are expired instead of
+				 // translated. They were created without a deposit, so nothing is held for their
+				 // owners. The old quizzes were keyed by the hash of their count, so every count up to
+				 // `QuizCnt` is looked up once, which is also what the step is weighed by. The counter
+				 // of `Quizzes` then starts from zero and replaces the separately kept
+				 // `ActiveQuizzes`. `QuizCnt` is kept, so the counts of the expired quizzes are never
+				 // handed out again
+				 let module = <Pallet<T>>::name().as_bytes();
+				 let now = <frame_system::Pallet<T>>::block_number();
+				 let latest = Self::get_latest_quiz();
+				 let mut expired : Weight = 0;
+				 for count in 1..=latest {
+					 let key = Twox64Concat::hash(&T::Hashing::hash_of(&count).encode());
+					 if storage::migration::have_storage_value(module, b"Quizzes", &key) {
+						 storage::migration::remove_storage_prefix(module, b"Quizzes", &key);
+						 expired = expired.saturating_add(1);
+						 Self::deposit_event(Event::QuizExpired(count, now));
+					 }
+					 // deleting a quiz used to leave its solution behind
+					 storage::migration::remove_storage_prefix(module, b"Solutions", &key);
+				 }
+				 let buckets = storage::migration::storage_iter::<Vec<T::Hash>>(module, b"QuizToDelete").drain().count() as Weight;
+				 <Quizzes<T>>::initialize_counter();
+				 storage::migration::remove_storage_prefix(module, b"ActiveQuizzes", &[]);
+				 log::info!(target: LOG_TARGET, "migrated to storage version 1, {} quizzes of the old layout expired", expired);
+				 weight = weight.saturating_add(T::DbWeight::get().reads_writes(
+					 latest.saturating_add(buckets).saturating_add(2),
+					 expired.saturating_mul(2).saturating_add(latest).saturating_add(buckets).saturating_add(2),
+				 ));
+			 }
+			 if version < 2 {
+				 // ratings were whole points and are now kept in hundredths of a point
+				 let mut rated : Weight = 0;
+				 <UserRating<T>>::translate::<u8, _>(|_, points| {
+					 rated = rated.saturating_add(1);
+					 Some(rating::from_points(points))
+				 });
+				 log::info!(target: LOG_TARGET, "migrated to storage version 2, {} ratings rescaled", rated);
+				 weight = weight.saturating_add(T::DbWeight::get().reads_writes(rated, rated));
+			 }
+			 if version < 3 {
+				 // scores were whole points and are now kept in hundredths of a point as well
+				 let mut rescaled : Weight = 0;
+				 <BestScores<T>>::translate::<(u8, T::BlockNumber), _>(|_, _, (score, achieved_at)| {
+					 rescaled = rescaled.saturating_add(1);
+					 Some(BestScore::<T> { score: rating::from_points(score), achieved_at })
+				 });
+				 <TournamentLeaders<T>>::translate::<Vec<(T::AccountId, (u8, T::BlockNumber))>, _>(|_, leaders| {
+					 rescaled = rescaled.saturating_add(1);
+					 Some(leaders.into_iter()
+						 .map(|(who, (score, achieved_at))| (who, BestScore::<T> { score: rating::from_points(score), achieved_at }))
+						 .collect())
+				 });
+				 <ScoreHistory<T>>::translate::<Vec<u8>, _>(|_, history| {
+					 rescaled = rescaled.saturating_add(1);
+					 Some(history.into_iter().map(rating::from_points).collect())
+				 });
+				 log::info!(target: LOG_TARGET, "migrated to storage version 3, {} score entries rescaled", rescaled);
+				 weight = weight.saturating_add(T::DbWeight::get().reads_writes(rescaled, rescaled));
+			 }
+			 if version < 4 {
+				 // earnings were locked in one `LockedEarnings` entry per owner and unlock block, they
+				 // are merged into the bounded per owner list of `EarningsLocks`
+				 let module = <Pallet<T>>::name().as_bytes();
+				 let now = <frame_system::Pallet<T>>::block_number();
+				 let mut moved : Weight = 0;
+				 for (key, amount) in storage::migration::storage_iter::<BalanceOf<T>>(module, b"LockedEarnings") {
+					 moved = moved.saturating_add(1);
+					 // the key is the `Twox64Concat` owner followed by the `Twox64Concat` unlock block
+					 let mut input = key.get(8..).unwrap_or_default();
+					 let owner = match T::AccountId::decode(&mut input) {
+						 Ok(owner) => owner,
+						 Err(_) => continue,
+					 };
+					 let mut input = input.get(8..).unwrap_or_default();
+					 let unlock_at = match T::BlockNumber::decode(&mut input) {
+						 Ok(unlock_at) => unlock_at,
+						 Err(_) => continue,
+					 };
+					 <EarningsLocks<T>>::mutate(&owner, |locks| Self::add_earnings_lock(locks, unlock_at, amount, now));
+				 }
+				 storage::migration::remove_storage_prefix(module, b"LockedEarnings", &[]);
+				 log::info!(target: LOG_TARGET, "migrated to storage version 4, {} earnings locks merged", moved);
+				 weight = weight.saturating_add(T::DbWeight::get().reads_writes(moved.saturating_mul(2), moved.saturating_add(1)));
+			 }
+			 if version < 5 {
+				 // the buckets of the deletion schedule were unbounded, the quizzes a bucket has no
+				 // room for any more are scheduled again, in another bucket or a later block
+				 let bound = T::MaxDeletionBucketLen::get() as usize;
+				 let mut buckets : Weight = 0;
+				 let mut overflow : Vec<T::Hash> = Vec::new();
+				 <QuizToDelete<T>>::translate::<Vec<T::Hash>, _>(|_, _, mut quiz_vec| {
+					 buckets = buckets.saturating_add(1);
+					 if quiz_vec.len() > bound {
+						 overflow.extend(quiz_vec.split_off(bound));
+					 }
+					 DeletionBucketOf::<T>::try_from(quiz_vec).ok()
+				 });
+				 let moved = overflow.len() as Weight;
+				 for quiz_id in overflow {
+					 if let Some(quiz) = Self::get_quiz(&quiz_id) {
+						 let expires_at = quiz.expires_at;
+						 Self::defer_expiry(&quiz_id, quiz, expires_at);
+					 }
+				 }
+				 log::info!(target: LOG_TARGET, "migrated to storage version 5, {} deletion buckets bounded, {} quizzes rescheduled", buckets, moved);
+				 weight = weight
+					 .saturating_add(T::DbWeight::get().reads_writes(buckets, buckets))
+					 .saturating_add(Self::schedule_weight().saturating_add(T::DbWeight::get().reads_writes(1, 1)).saturating_mul(moved));
+			 }
+			 STORAGE_VERSION.put::<Pallet<T>>();
+			 weight
+		 }
+
+		 // this version of FRAME has no `try_state` hook, so the invariants are checked after every
+		 // runtime upgrade run through try-runtime
+		 #[cfg(feature = "try-runtime")]
+		 fn post_upgrade() -> Result<(), &'static str> {
+			 Self::do_try_state()
+		 }
+
+		 // and in place of `try_state`, at the end of every block of a try-runtime build, so
+		 // `try-runtime execute-block` fails on a block that breaks them. Never compiled into a
+		 // production runtime, the checks go through all quizzes without being weighed
+		 #[cfg(feature = "try-runtime")]
+		 fn on_finalize(_now: T::BlockNumber) {
+			 Self::do_try_state().expect("the storage invariants of the quiz pallet hold after every block");
+		 }
+	 }
+
+	 #[pallet::call]
+    impl<T: Config> Pallet<T> {
+
+		#[pallet::weight(<Pallet<T>>::create_quiz_weight(QUESTIONS_PER_QUIZ))]
+		pub fn add_quiz(
+			origin: OriginFor<T>,
+			question1: Question,
+			question2: Question,
+			question3: Question,
+			question4: Question,
+			question5: Question,
+			solution: Solution,
+			rating: u8,
+			lang: LanguageCode,
+		) -> DispatchResult {
+			let sender = T::CreateOrigin::ensure_origin(origin)?;
+			let mut _questions = Vec::new();
+			_questions.push(question1);
+			_questions.push(question2);
+			_questions.push(question3);
 			_questions.push(question4);
 			_questions.push(question5);
+			Self::create_quiz(sender, _questions, lang, solution, rating)?;
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::create_quiz_weight(QUESTIONS_PER_QUIZ))]
+		pub fn add_quiz_encoded(
+			origin: OriginFor<T>,
+			payload: Vec<u8>,
+		) -> DispatchResult {
+			let sender = T::CreateOrigin::ensure_origin(origin)?;
+
+			// the whole payload has to decode, trailing bytes are rejected as well
+			let mut input = &payload[..];
+			let quiz = QuizPayload::decode(&mut input).map_err(|_| <Error<T>>::DecodeFailed)?;
+			ensure!(input.is_empty(), <Error<T>>::DecodeFailed);
+			ensure!(quiz.questions.len() == QUESTIONS_PER_QUIZ, <Error<T>>::DecodeFailed);
+
+			Self::create_quiz(sender, quiz.questions, quiz.lang, quiz.solution, quiz.rating)?;
+			Ok(())
+		}
+
+		// the batch is atomic, a single invalid quiz rejects the whole call and none are created
+		#[pallet::weight(<Pallet<T>>::create_quiz_weight(QUESTIONS_PER_QUIZ).saturating_mul(quizzes.len() as Weight))]
+		#[transactional]
+		pub fn add_quizzes(
+			origin: OriginFor<T>,
+			quizzes: Vec<QuizInput>,
+		) -> DispatchResult {
+			let sender = T::CreateOrigin::ensure_origin(origin)?;
+			ensure!(quizzes.len() <= T::MaxQuizBatch::get() as usize, <Error<T>>::BatchTooLarge);
+
+			let mut created = Vec::with_capacity(quizzes.len());
+			for quiz in quizzes {
+				// the weight is charged for full quizzes only
+				ensure!(quiz.questions.len() == QUESTIONS_PER_QUIZ, <Error<T>>::QuestionCountMismatch);
+				created.push(Self::create_quiz(sender.clone(), quiz.questions, quiz.lang, quiz.solution, quiz.rating)?);
+			}
+			Self::deposit_event(Event::QuizzesCreated(sender, created));
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::create_quiz_weight(QUESTIONS_PER_QUIZ).saturating_add(T::DbWeight::get().reads(2)))]
+		pub fn clone_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64
+		) -> DispatchResult {
+			let sender = T::CreateOrigin::ensure_origin(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			// only owners may clone, copying someone else's quiz would hand out its solution
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			let solution = Self::get_solution(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			// the copy is an independent quiz with its own id, deposit, expiry and counters
+			let cloned = Self::create_quiz(sender.clone(), quiz.questions, quiz.lang, solution, quiz.rating)?;
+			<QuizSources<T>>::insert(T::Hashing::hash_of(&cloned), quiz_id);
+			Self::deposit_event(Event::QuizCloned(quiz_count, cloned, sender));
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::attempt_quiz_weight())]
+		pub fn attempt_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			submission: Solution,
+			referrer: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::submit_attempt(sender, quiz_count, submission, referrer)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(<Pallet<T>>::attempt_quiz_weight())]
+		pub fn attempt_quiz_with_receipt(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			submission: Solution,
+			referrer: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			// a dispatch cannot return data, the receipt reaches integrators through the event
+			let receipt = Self::submit_attempt(sender.clone(), quiz_count, submission, referrer)?;
+			Self::deposit_event(Event::AttemptReceiptIssued(quiz_count, sender, receipt));
+			Ok(().into())
+		}
+
+		#[pallet::weight(<Pallet<T>>::remove_quiz_weight()
+			.saturating_add(<Pallet<T>>::unschedule_weight())
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn delete_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64
+		) -> DispatchResult {
+			// function body starts here
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			// ensuring that only the quiz owner can set the quiz for deletion
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			Self::unschedule_deletion(&quiz_id, quiz.expires_at);
+			Self::remove_quiz(&quiz_id, &quiz);
+			Self::deposit_event(Event::QuizDeleted(quiz_count, sender, <frame_system::Pallet<T>>::block_number()));
+			Ok(())
+			// function body ends here
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(<Pallet<T>>::questions_weight(questions.len())).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn add_quiz_translation(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			lang: LanguageCode,
+			questions: Vec<Question>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			// the default language is kept in the quiz itself and cannot be replaced
+			ensure!(lang != quiz.lang, <Error<T>>::LanguageMismatch);
+			// every translation is scored against the same solution so it has to line up question by question
+			ensure!(questions.len() == quiz.questions.len(), <Error<T>>::QuestionCountMismatch);
+			Self::validate_language(&questions, lang)?;
+			Self::validate_text(&questions)?;
+			ensure!(T::OnQuizProposed::approve(&sender, &questions), <Error<T>>::ContentRejected);
+
+			<QuizTranslations<T>>::insert(&quiz_id, lang, questions);
+			Self::deposit_event(Event::QuizTranslated(quiz_count, lang));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(<Pallet<T>>::questions_weight(questions.len())).saturating_add(T::DbWeight::get().reads_writes(5, 5)))]
+		pub fn edit_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			questions: Vec<Question>,
+			solution: Solution,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let mut quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			ensure!(questions.len() == quiz.questions.len(), <Error<T>>::QuestionCountMismatch);
+			Self::validate_language(&questions, quiz.lang)?;
+			Self::validate_text(&questions)?;
+			Self::validate_solution(&solution, &questions)?;
+			// grades already handed out must not change retroactively, as in `update_solution`, so
+			// an attempted quiz can only be edited with its answer key unchanged
+			ensure!(
+				Self::get_attempt_count(&quiz_id) == 0 || Self::get_solution(&quiz_id).as_ref() == Some(&solution),
+				<Error<T>>::QuizAlreadyAttempted
+			);
+			ensure!(T::OnQuizProposed::approve(&sender, &questions), <Error<T>>::ContentRejected);
+			Self::adjust_deposit(&mut quiz, &questions)?;
+
+			quiz.questions = questions;
+			quiz.version = quiz.version.saturating_add(1);
+			let version = quiz.version;
+			<Quizzes<T>>::insert(&quiz_id, quiz);
+			<Solutions<T>>::insert(&quiz_id, solution);
+			// translations and explanations no longer line up with the edited questions
+			let _ = <QuizTranslations<T>>::remove_prefix(&quiz_id, None);
+			<Explanations<T>>::remove(&quiz_id);
+
+			Self::deposit_event(Event::QuizEdited(quiz_count, version));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn update_solution(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			solution: Solution,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			// grades already handed out must not change retroactively
+			ensure!(Self::get_attempt_count(&quiz_id) == 0, <Error<T>>::QuizAlreadyAttempted);
+			Self::validate_solution(&solution, &quiz.questions)?;
+
+			<Solutions<T>>::insert(&quiz_id, solution);
+			Self::deposit_event(Event::SolutionUpdated(quiz_count));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(<Pallet<T>>::questions_weight(pool.len())).saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn set_question_pool(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			pool: Vec<Question>,
+			answers: Vec<Answer>,
+			draw: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			ensure!(Self::get_attempt_count(&quiz_id) == 0, <Error<T>>::QuizAlreadyAttempted);
+			// a submission holds `QUESTIONS_PER_QUIZ` answers, an attempt cannot be asked more
+			ensure!(draw >= 1 && draw as usize <= QUESTIONS_PER_QUIZ, <Error<T>>::InvalidDrawCount);
+			ensure!(pool.len() >= draw as usize, <Error<T>>::PoolTooSmall);
+			ensure!(answers.len() == pool.len(), <Error<T>>::AnswerCountMismatch);
+			Self::validate_language(&pool, quiz.lang)?;
+			Self::validate_text(&pool)?;
+			ensure!(T::OnQuizProposed::approve(&sender, &pool), <Error<T>>::ContentRejected);
+
+			// every pooled question needs a real option, or set of them, as its answer
+			ensure!(
+				pool.iter().zip(answers.iter()).all(|(question, answer)| Self::is_valid_key(question, *answer)),
+				<Error<T>>::InvalidOptionProvided
+			);
+			let entries : Vec<(Question, Answer)> = pool.into_iter().zip(answers).collect();
+			let pool_size = entries.len() as u32;
+			<QuestionPools<T>>::insert(&quiz_id, entries);
+			<PoolDraws<T>>::insert(&quiz_id, draw);
+			Self::deposit_event(Event::QuestionPoolSet(quiz_count, pool_size, draw));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(<Pallet<T>>::questions_weight(explanations.len())).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn set_explanations(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			explanations: Vec<ExplanationOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			ensure!(explanations.len() == quiz.questions.len(), <Error<T>>::QuestionCountMismatch);
+
+			<Explanations<T>>::insert(&quiz_id, explanations);
+			Self::deposit_event(Event::ExplanationsSet(quiz_count));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(4, 2)))]
+		pub fn rate_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			stars: u8,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(<Quizzes<T>>::contains_key(&quiz_id), <Error<T>>::QuizDoesNotExist);
+			ensure!(stars > 0 && stars <= MAX_STARS, <Error<T>>::InvalidStars);
+			ensure!(<AttemptResults<T>>::contains_key(&quiz_id, &sender), <Error<T>>::MustAttemptFirst);
+
+			// rating again replaces the player's previous rating
+			let previous = <StarsGiven<T>>::get(&quiz_id, &sender);
+			<QuizQuality<T>>::mutate(&quiz_id, |quality| {
+				match previous {
+					Some(previous) => quality.sum = quality.sum.saturating_sub(previous as u32),
+					None => quality.count = quality.count.saturating_add(1),
+				}
+				quality.sum = quality.sum.saturating_add(stars as u32);
+			});
+			<StarsGiven<T>>::insert(&quiz_id, &sender, stars);
+
+			Self::deposit_event(Event::QuizRated(quiz_count, sender, stars));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn set_quiz_stake(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			stake: BalanceOf<T>,
+			pass_threshold: u8,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let mut quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			// players already charged under the old terms must not be graded differently
+			ensure!(Self::get_attempt_count(&quiz_id) == 0, <Error<T>>::QuizAlreadyAttempted);
+
+			quiz.stake = stake;
+			quiz.pass_threshold = pass_threshold;
+			<Quizzes<T>>::insert(&quiz_id, quiz);
+			Self::deposit_event(Event::QuizStakeSet(quiz_count, stake, pass_threshold));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn set_attempt_limit(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			max_attempts_per_user: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let mut quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(Self::is_owner(quiz_count, &sender), <Error<T>>::NotTheQuizOwner);
+			// players who already attempted under a limit must not be locked out by a lower one, 0
+			// lifts the limit and always counts as raising it
+			let lowered = max_attempts_per_user != 0
+				&& (quiz.max_attempts_per_user == 0 || max_attempts_per_user < quiz.max_attempts_per_user);
+			ensure!(!lowered || Self::get_attempt_count(&quiz_id) == 0, <Error<T>>::QuizAlreadyAttempted);
+
+			quiz.max_attempts_per_user = max_attempts_per_user;
+			<Quizzes<T>>::insert(&quiz_id, quiz);
+			Self::deposit_event(Event::AttemptLimitSet(quiz_count, max_attempts_per_user));
+			Ok(())
+		}
+
+		// the quiz, the rating, the attempt in progress with its count, the 12 eligibility items, a
+		// cancelled seed, the question pool with its draw and the balance are read, the reserve, the
+		// start, the count, the cancelled seed and the drawn questions written
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(20, 5)))]
+		pub fn start_attempt(
+			origin: OriginFor<T>,
+			quiz_count: u64
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			let is_stretch = Self::ensure_eligible(&quiz_id, &quiz, &sender, <UserRating<T>>::try_get(&sender).ok(), false)?;
+			ensure!(!<AttemptsInProgress<T>>::contains_key(&quiz_id, &sender), <Error<T>>::AttemptAlreadyInProgress);
+			let max_concurrent = T::MaxConcurrentAttempts::get();
+			ensure!(
+				max_concurrent == 0 || Self::get_attempts_in_progress_count(&quiz_id) < max_concurrent,
+				<Error<T>>::QuizBusy
+			);
+
+			// reserving the worst case cost, stretch premium and fee included, so the submission can
+			// always be paid for
+			let reserved = Self::max_charge(&quiz, is_stretch);
+			T::Currency::reserve(&sender, reserved).map_err(|_| <Error<T>>::InsufficientBalance)?;
+
+			let started_at = <frame_system::Pallet<T>>::block_number();
+			// computed once and stored, so the order shown is the order scored however long the
+			// attempt takes. A cancelled attempt hands its seed on, so cancelling and starting again
+			// never rerolls the option order or the drawn questions
+			let seed = <CancelledSeeds<T>>::take(&quiz_id, &sender).unwrap_or_else(|| {
+				T::Hashing::hash_of(&(<frame_system::Pallet<T>>::parent_hash(), &quiz_id, &sender, started_at))
+			});
+			<AttemptsInProgress<T>>::insert(&quiz_id, &sender, AttemptStart::<T> { started_at, reserved, seed });
+			<AttemptsInProgressCount<T>>::mutate(&quiz_id, |count| *count = count.saturating_add(1));
+			if let Some(pool) = Self::get_question_pool(&quiz_id) {
+				let drawn = Self::draw_questions(&quiz_id, &sender, seed, pool.len() as u32, Self::questions_per_attempt(&quiz_id, &quiz));
+				<DrawnQuestions<T>>::insert(&quiz_id, &sender, drawn);
+			}
+			Self::deposit_event(Event::AttemptStarted(quiz_count, sender));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(4, 5)))]
+		pub fn cancel_attempt(
+			origin: OriginFor<T>,
+			quiz_count: u64
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+
+			let start = <AttemptsInProgress<T>>::take(&quiz_id, &sender).ok_or(<Error<T>>::NoAttemptInProgress)?;
+			T::Currency::unreserve(&sender, start.reserved);
+			Self::end_attempt_in_progress(&quiz_id);
+			// the next start reuses the seed and draws the same questions from it again
+			<CancelledSeeds<T>>::insert(&quiz_id, &sender, start.seed);
+			<DrawnQuestions<T>>::remove(&quiz_id, &sender);
+
+			Self::deposit_event(Event::AttemptCancelled(quiz_count, sender));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn block_linked_account(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			who: T::AccountId,
+		) -> DispatchResult {
+			// owners cannot populate the list themselves, only governance can
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(<Quizzes<T>>::contains_key(&quiz_id), <Error<T>>::QuizDoesNotExist);
+
+			<LinkedAccounts<T>>::insert(&quiz_id, &who, ());
+			Self::deposit_event(Event::LinkedAccountBlocked(quiz_count, who));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn unblock_linked_account(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			who: T::AccountId,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+
+			<LinkedAccounts<T>>::remove(&quiz_id, &who);
+			Self::deposit_event(Event::LinkedAccountUnblocked(quiz_count, who));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn ban_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResult {
+			// quizzes the account already owns stay until they expire
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			<BannedAccounts<T>>::insert(&who, ());
+			Self::deposit_event(Event::AccountBanned(who));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn unban_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			<BannedAccounts<T>>::remove(&who);
+			Self::deposit_event(Event::AccountUnbanned(who));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn prune_expired_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+		) -> DispatchResult {
+			// the deposit of an expired quiz is refunded, so what it leaves behind for review is only
+			// removed once governance no longer needs it
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(
+				<ArchivedQuizzes<T>>::contains_key(&quiz_id) || <RevealedSolutions<T>>::contains_key(&quiz_id),
+				<Error<T>>::NothingToPrune
+			);
+
+			<ArchivedQuizzes<T>>::remove(&quiz_id);
+			<RevealedSolutions<T>>::remove(&quiz_id);
+			Self::deposit_event(Event::ExpiredQuizPruned(quiz_count));
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::remove_quiz_weight()
+			.saturating_add(<Pallet<T>>::unschedule_weight())
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+			.saturating_add(if *refund { <Pallet<T>>::refunds_weight() } else { 0 }))]
+		pub fn force_expire_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			refund: bool,
+		) -> DispatchResult {
+			// a way out for quizzes the deletion schedule lost track of, or found to be defective
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			// unscheduling is a no-op when the schedule entry is already missing
+			Self::unschedule_deletion(&quiz_id, quiz.expires_at);
+			if let Some(solution) = Self::get_solution(&quiz_id) {
+				<RevealedSolutions<T>>::insert(&quiz_id, solution);
+			}
+			if refund {
+				// the deposit stays reserved to pay the refunds out of, so it is not released with the quiz
+				<PendingRefunds<T>>::insert(&quiz_id, (quiz.owner.clone(), quiz.deposit));
+				Self::remove_quiz(&quiz_id, &Quiz::<T> { deposit: Zero::zero(), ..quiz });
+				Self::deposit_event(Event::QuizForceExpired(quiz_count));
+				Self::process_refunds(quiz_count);
+			} else {
+				Self::remove_quiz(&quiz_id, &quiz);
+				Self::deposit_event(Event::QuizForceExpired(quiz_count));
+			}
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::refunds_weight())]
+		pub fn continue_refunds(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+		) -> DispatchResult {
+			// anyone may push the refunds of a defective quiz along
+			ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(<PendingRefunds<T>>::contains_key(&quiz_id), <Error<T>>::NoRefundsPending);
+
+			Self::process_refunds(quiz_count);
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(7, 9)))]
+		pub fn reset_my_rating(
+			origin: OriginFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			// the fee and the cooldown keep players from farming easy quizzes with repeated resets
+			ensure!(
+				Self::get_last_rating_reset(&sender)
+					.map_or(true, |reset_at| now >= reset_at.saturating_add(T::RatingResetCooldown::get())),
+				<Error<T>>::RatingResetTooSoon
+			);
+			let fee = T::RatingResetFee::get();
+			Self::charge_attempt_fee(&sender, fee)?;
+
+			// back to a provisional player whose next scores build the rating up from 0
+			Self::set_user_rating(&sender, 0, <UserRating<T>>::try_get(&sender).ok());
+			<UserAttempts<T>>::remove(&sender);
+			<ScoreHistory<T>>::remove(&sender);
+			Self::update_leaderboard(sender.clone(), 0);
+			<LastRatingReset<T>>::insert(&sender, now);
+			Self::deposit_event(Event::RatingReset(sender, fee));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(5, 4)))]
+		pub fn recompute_user_rating(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResult {
+			// brings a stored rating in line with the current formula, e.g. after it changed
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let rating = Self::recompute_rating(&who).ok_or(<Error<T>>::NoScoreHistory)?;
+			Self::set_user_rating(&who, rating, <UserRating<T>>::try_get(&who).ok());
+			let points = rating::to_points(rating);
+			Self::update_leaderboard(who.clone(), points);
+			Self::deposit_event(Event::RatingRecomputed(who, points));
+			Ok(())
+		}
+
+		#[pallet::weight(<Pallet<T>>::bulk_delete_weight((*limit).min(T::MaxBulkDelete::get())))]
+		pub fn delete_all_my_quizzes(
+			origin: OriginFor<T>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			// every key visited is removed, so the index is its own cursor: the next call picks up
+			// where this one stopped, until the event reports nothing remaining. Collecting first so
+			// the index is not mutated while it is iterated
+			let to_delete : Vec<T::Hash> = <OwnedQuizzes<T>>::iter_key_prefix(&sender)
+				.take(limit.min(T::MaxBulkDelete::get()) as usize)
+				.collect();
+			let mut deleted : u32 = 0;
+			for quiz_id in to_delete {
+				match Self::get_quiz(&quiz_id) {
+					Some(quiz) => {
+						Self::unschedule_deletion(&quiz_id, quiz.expires_at);
+						Self::remove_quiz(&quiz_id, &quiz);
+						Self::deposit_event(Event::QuizDeleted(quiz.count, sender.clone(), <frame_system::Pallet<T>>::block_number()));
+					},
+					None => {
+						<OwnedQuizzes<T>>::remove(&sender, &quiz_id);
+						<OwnedQuizCount<T>>::mutate(&sender, |count| *count = count.saturating_sub(1));
+					},
+				}
+				deleted += 1;
+			}
+
+			let remaining = Self::get_owned_quiz_count(&sender);
+			Self::deposit_event(Event::OwnerQuizzesDeleted(sender, deleted, remaining));
+			Ok(Some(Self::bulk_delete_weight(deleted)).into())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(4, 4)))]
+		pub fn fund_prize_pool(
+			origin: OriginFor<T>,
+			quiz_count: u64,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(<Quizzes<T>>::contains_key(&quiz_id), <Error<T>>::QuizDoesNotExist);
+
+			// funders are remembered so a pool nobody competed for goes back to them, not the owner
+			let mut funders = Self::get_prize_funders(&quiz_id);
+			match funders.iter_mut().find(|(funder, _)| funder == &sender) {
+				Some((_, funded)) => *funded = funded.saturating_add(amount),
+				None => {
+					ensure!((funders.len() as u32) < MAX_PRIZE_FUNDERS, <Error<T>>::TooManyFunders);
+					funders.push((sender.clone(), amount));
+				}
+			}
+			T::Currency::transfer(&sender, &Self::account_id(), amount, ExistenceRequirement::KeepAlive)
+				.map_err(|_| <Error<T>>::InsufficientBalance)?;
+			<PrizeFunders<T>>::insert(&quiz_id, funders);
+			<PrizePools<T>>::mutate(&quiz_id, |pool| *pool = pool.saturating_add(amount));
+			Self::deposit_event(Event::PrizePoolFunded(quiz_count, sender, amount));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(3, 3)))]
+		pub fn claim_prize(
+			origin: OriginFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let amount = Self::get_unclaimed_prize(&sender);
+			ensure!(!amount.is_zero(), <Error<T>>::NoPrizeToClaim);
+
+			T::Currency::transfer(&Self::account_id(), &sender, amount, ExistenceRequirement::AllowDeath)?;
+			<UnclaimedPrizes<T>>::remove(&sender);
+			Self::deposit_event(Event::PrizeClaimed(sender, amount));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn claim_earnings(
+			origin: OriginFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			// an owner has at most three locks, so claiming is one read and one write
+			let mut locks = Self::get_earnings_locks(&sender);
+			let claimed = locks.iter()
+				.filter(|(unlock_at, _)| *unlock_at <= now)
+				.fold(BalanceOf::<T>::zero(), |claimed, (_, amount)| claimed.saturating_add(*amount));
+			ensure!(!claimed.is_zero(), <Error<T>>::NoEarningsToClaim);
+
+			locks.retain(|(unlock_at, _)| *unlock_at > now);
+			if locks.is_empty() {
+				<EarningsLocks<T>>::remove(&sender);
+			} else {
+				<EarningsLocks<T>>::insert(&sender, locks);
+			}
+			T::Currency::unreserve(&sender, claimed);
+
+			Self::deposit_event(Event::EarningsClaimed(sender, claimed));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn toggle_monetization_opt_out(
+			origin: OriginFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let opted_out = !<MonetizationOptOut<T>>::contains_key(&sender);
+			if opted_out {
+				<MonetizationOptOut<T>>::insert(&sender, ());
+			} else {
+				<MonetizationOptOut<T>>::remove(&sender);
+			}
+			Self::deposit_event(Event::MonetizationOptOutSet(sender, opted_out));
+			Ok(())
+		}
+
+		#[pallet::weight(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads_writes(5, 5)))]
+		pub fn report_quiz(
+			origin: OriginFor<T>,
+			quiz_count: u64
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let mut quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+
+			ensure!(sender != quiz.owner, <Error<T>>::OwnerCannotReportQuiz);
+			ensure!(!<QuizReports<T>>::contains_key(&quiz_id, &sender), <Error<T>>::AlreadyReported);
+			// only players who attempted the quiz count, fresh accounts cannot slash a creator
+			ensure!(<AttemptResults<T>>::contains_key(&quiz_id, &sender), <Error<T>>::ReporterNotVerified);
+
+			<QuizReports<T>>::insert(&quiz_id, &sender, ());
+			let reports = <ReportCount<T>>::mutate(&quiz_id, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+			Self::deposit_event(Event::QuizReported(quiz_count, sender, reports));
+
+			// past the threshold the deposit is slashed instead of being refunded on deletion
+			if reports >= T::SlashReportThreshold::get() && !quiz.deposit.is_zero() {
+				let (imbalance, missing) = T::Currency::slash_reserved(&quiz.owner, quiz.deposit);
+				T::Slashed::on_unbalanced(imbalance);
+				let slashed = quiz.deposit.saturating_sub(missing);
+				Self::deposit_event(Event::CreatorSlashed(quiz_count, quiz.owner.clone(), slashed));
+				quiz.deposit = Zero::zero();
+				<Quizzes<T>>::insert(&quiz_id, quiz);
+			}
+			Ok(())
+		}
+    }
+
+	impl<T:Config> Pallet<T> {
+		//Helper functions here
+
+		// the weight of handling `count` questions
+		pub fn questions_weight(count: usize) -> Weight {
+			PER_QUESTION_WEIGHT.saturating_mul(count as Weight)
+		}
+
+		// `create_quiz` reads the counters, the deletion bucket and the owner's account and writes
+		// all of them together with the quiz, its solution and the owner index
+		pub fn create_quiz_weight(questions: usize) -> Weight {
+			BASE_WEIGHT
+				.saturating_add(Self::questions_weight(questions))
+				.saturating_add(T::DbWeight::get().reads_writes(5, 9))
+				.saturating_add(Self::schedule_weight())
+		}
+
+		// a quiz may be tried in every bucket of `DELETION_SPILL_BLOCKS` blocks before one takes it
+		pub fn schedule_weight() -> Weight {
+			T::DbWeight::get().reads_writes((DELETION_BUCKETS as Weight).saturating_mul(DELETION_SPILL_BLOCKS as Weight), 1)
+		}
+
+		// a scheduled quiz may sit in any bucket of its block
+		pub fn unschedule_weight() -> Weight {
+			T::DbWeight::get().reads_writes(DELETION_BUCKETS as Weight, 1)
+		}
+
+		// `remove_quiz` clears every item stored alongside a quiz and refunds the deposit
+		pub fn remove_quiz_weight() -> Weight {
+			BASE_WEIGHT
+				.saturating_add(T::DbWeight::get().reads_writes(6, 19))
+				.saturating_add(T::DbWeight::get().reads_writes(4, 4).saturating_mul(T::TournamentWinners::get() as Weight))
+				.saturating_add(T::DbWeight::get().reads_writes(2, 3).saturating_mul(MAX_PRIZE_FUNDERS as Weight))
+		}
+
+		// `delete_all_my_quizzes` removing `count` quizzes, each looked up through the owner index
+		pub fn bulk_delete_weight(count: u32) -> Weight {
+			BASE_WEIGHT.saturating_add(
+				Self::remove_quiz_weight()
+					.saturating_add(Self::unschedule_weight())
+					.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+					.saturating_mul(count as Weight)
+			)
+		}
+
+		// expiring a quiz reveals its solution and may archive it on top of removing it
+		pub fn expire_quiz_weight() -> Weight {
+			Self::remove_quiz_weight().saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		}
+
+		// the worst case of a scored attempt, a started attempt on a pooled quiz paid with a referrer.
+		// It reads the 18 items the checks need, the player, referrer and owner accounts, the total
+		// issuance, the earnings lock, the charges, the attempt and answer counts, the best score and
+		// tournament leaders, both rating counts, the score history, the leaderboard and the result.
+		// It writes 23 items, everything above except the reads only made for the checks
+		pub fn attempt_quiz_weight() -> Weight {
+			BASE_WEIGHT
+				.saturating_add(Self::questions_weight(QUESTIONS_PER_QUIZ))
+				.saturating_add(T::DbWeight::get().reads_writes(34, 23))
+		}
+
+		// an attempt rejected before any scoring or transfer only reads what the eligibility checks
+		// need: the quiz, the rating, the attempt in progress, 12 eligibility items, the pool draw
+		// and the drawn questions with their pool
+		pub fn rejected_attempt_weight() -> Weight {
+			BASE_WEIGHT.saturating_add(T::DbWeight::get().reads(18))
+		}
+
+		// validates and stores a new quiz, returning its count. Dispatch is not transactional in this
+		// version of FRAME, so a failure after the deposit is reserved or the deletion is scheduled
+		// rolls everything back instead of leaving a half-created quiz
+		#[transactional]
+		pub fn create_quiz(
+			owner: T::AccountId,
+			questions: Vec<Question>,
+			lang: LanguageCode,
+			solution: Solution,
+			rating: u8,
+		) -> Result<u64, DispatchError> {
+			ensure!(!<BannedAccounts<T>>::contains_key(&owner), <Error<T>>::AccountBanned);
+			ensure!(Self::get_active_quizzes() < T::MaxActiveQuizzes::get(), <Error<T>>::TooManyActiveQuizzes);
+			// a rating no player can reach would make the quiz impossible to attempt
+			ensure!(rating <= T::MaxRating::get(), <Error<T>>::RatingTooHighForQuiz);
+			Self::validate_language(&questions, lang)?;
+			Self::validate_text(&questions)?;
+			Self::validate_solution(&solution, &questions)?;
+			ensure!(T::OnQuizProposed::approve(&owner, &questions), <Error<T>>::ContentRejected);
+			let quiz_count = Self::get_latest_quiz() + 1;
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			// the id only derives from the count, a `QuizCnt` set too low must not overwrite a live quiz
+			ensure!(!<Quizzes<T>>::contains_key(&quiz_id), <Error<T>>::QuizIdCollision);
+			let deposit = Self::quiz_deposit(&questions);
+			T::Currency::reserve(&owner, deposit).map_err(|_| <Error<T>>::InsufficientBalance)?;
+
+			let the_end_block_number = <frame_system::Pallet<T>>::block_number();
+			let expires_at = Self::add_quiz_to_be_deleted(the_end_block_number, quiz_count)?;
 			let quiz = Quiz::<T> {
-				owner: sender.clone(),
-				questions: _questions,
+				count: quiz_count,
+				owner: owner.clone(),
+				questions,
+				lang,
 				rating: rating.clone(),
+				deposit,
+				expires_at,
+				stake: Zero::zero(),
+				pass_threshold: 0,
+				version: 0,
+				max_attempts_per_user: 0,
+			};
+			<Quizzes<T>>::insert(quiz_id.clone(), quiz);
+			<Solutions<T>>::insert(quiz_id, solution);
+			<QuizCnt<T>>::put(quiz_count);
+			<QuizByIndex<T>>::insert(quiz_count, quiz_id);
+			<OwnedQuizzes<T>>::insert(&owner, &quiz_id, ());
+			<OwnedQuizCount<T>>::mutate(&owner, |count| *count = count.saturating_add(1));
+
+			log::info!(target: LOG_TARGET, "quiz {} created by {:?} with rating {}", quiz_count, owner, rating);
+			Self::deposit_event(Event::QuizCreated(quiz_count, owner, rating));
+			Ok(quiz_count)
+		}
+
+		// ensures every question tagged with a language belongs to the set's language
+		pub fn validate_language(
+			questions: &[Question],
+			lang: LanguageCode,
+		) -> Result<(), Error<T>> {
+			ensure!(
+				questions.iter().all(|question| question.lang.map_or(true, |tag| tag == lang)),
+				<Error<T>>::LanguageMismatch
+			);
+			Ok(())
+		}
+
+		// ensures every statement and option of the questions has at least `T::MinTextLen` bytes
+		pub fn validate_text(
+			questions: &[Question],
+		) -> Result<(), Error<T>> {
+			let min = T::MinTextLen::get() as usize;
+			ensure!(
+				questions.iter().all(|question| {
+					[&question.statement, &question.option1, &question.option2, &question.option3, &question.option4]
+						.iter()
+						.all(|text| text.len() >= min)
+				}),
+				<Error<T>>::TextTooShort
+			);
+			Ok(())
+		}
+
+		// the questions of a quiz as shown to a player, without any of the owner's bookkeeping
+		pub fn quiz_questions(
+			quiz_count: u64,
+		) -> Option<Vec<Question>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			Self::get_quiz(&quiz_id).map(|quiz| quiz.questions)
+		}
+
+		// the questions of a quiz in the given language, the default language is always available
+		pub fn get_quiz_lang(
+			quiz_count: u64,
+			lang: LanguageCode,
+		) -> Option<Vec<Question>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id)?;
+			if lang == quiz.lang {
+				Some(quiz.questions)
+			} else {
+				Self::get_translation(&quiz_id, lang)
+			}
+		}
+
+		// checks that an answer key has one answer per question and picks an option for each of
+		// them, abstaining is only meaningful in a submission
+		pub fn validate_solution(
+			solution: &Solution,
+			questions: &[Question],
+		) -> Result<(), Error<T>> {
+			ensure!(solution.answer_count() == questions.len(), <Error<T>>::SolutionShapeMismatch);
+			ensure!(
+				questions.iter().zip(solution.answers().iter()).all(|(question, answer)| Self::is_valid_key(question, *answer)),
+				<Error<T>>::InvalidOptionProvided
+			);
+			// the same option everywhere makes a quiz guessable, some chains refuse such keys
+			let answers = solution.answers();
+			ensure!(
+				!T::RejectUniformSolutions::get() || answers.iter().any(|answer| *answer != answers[0]),
+				<Error<T>>::SolutionTooUniform
+			);
+			Ok(())
+		}
+
+		// whether `answer` can be the key of `question`: a single option for ordinary questions and a
+		// non-empty set of existing options for multi-select ones
+		pub fn is_valid_key(
+			question: &Question,
+			answer: Answer,
+		) -> bool {
+			match answer {
+				Answer::Several(options) => question.multi_select && options != 0 && options == answer.options(),
+				answer => !question.multi_select && answer.index().is_some(),
+			}
+		}
+
+		// the seed of a started attempt's option shuffle, stored in the start record. It mixes the
+		// parent hash of the start block with the quiz, player and start block, and never changes
+		// for the attempt, so `option_order` queried in the start block already shows the order the
+		// submission is scored against. Only a submission lets the next attempt get a new one
+		pub fn attempt_seed(
+			start: &AttemptStart<T>,
+		) -> T::Hash {
+			start.seed
+		}
+
+		// the order in which `who` is shown the options of a question, display position `i` holds
+		// option `order[i]`. Without an attempt seed it only depends on quiz id and account, so it is
+		// stable but predictable; with one it is fixed per started attempt. It is never stored
+		pub fn option_permutation(
+			quiz_id: &T::Hash,
+			who: &T::AccountId,
+			attempt_seed: Option<T::Hash>,
+			question: u8,
+		) -> [u8; OPTIONS_PER_QUESTION as usize] {
+			let mut order = [0u8; OPTIONS_PER_QUESTION as usize];
+			for (index, option) in order.iter_mut().enumerate() {
+				*option = index as u8;
+			}
+			// Fisher-Yates with a fresh hash per swap, so no step reuses the entropy of another
+			for i in (1..order.len()).rev() {
+				let step = T::Hashing::hash_of(&(quiz_id, who, attempt_seed, question, i as u8));
+				order.swap(i, Self::random_below(step, i + 1));
+			}
+			order
+		}
+
+		// an index below `bound` taken from the first eight bytes of `entropy`. With 64 bits drawn for
+		// bounds this small the modulo bias is below `bound / 2^64`
+		pub fn random_below(
+			entropy: T::Hash,
+			bound: usize,
+		) -> usize {
+			let random = entropy.as_ref().iter().take(8).fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+			(random % bound.max(1) as u64) as usize
+		}
+
+		// the option order of every question of a quiz as shown to `who`
+		pub fn option_order(
+			quiz_count: u64,
+			who: &T::AccountId,
+		) -> Option<Vec<[u8; OPTIONS_PER_QUESTION as usize]>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id)?;
+			let seed = Self::get_attempt_in_progress(&quiz_id, who).as_ref().map(Self::attempt_seed);
+			Some((0..Self::questions_per_attempt(&quiz_id, &quiz) as u8).map(|question| Self::option_permutation(&quiz_id, who, seed, question)).collect())
+		}
+
+		// maps the displayed positions a player submitted back to the underlying options
+		pub fn unshuffle_submission(
+			quiz_id: &T::Hash,
+			who: &T::AccountId,
+			attempt_seed: Option<T::Hash>,
+			submission: Solution,
+		) -> Solution {
+			let unshuffle = |question: u8, answer: Answer| {
+				let order = Self::option_permutation(quiz_id, who, attempt_seed, question);
+				if let Answer::Several(positions) = answer {
+					return Answer::Several((0..OPTIONS_PER_QUESTION)
+						.filter(|position| positions & 1 << position != 0)
+						.filter_map(|position| order.get(position as usize).copied())
+						.fold(0u8, |options, option| options | 1 << option));
+				}
+				answer.index()
+					.and_then(|position| order.get(position as usize).copied())
+					.and_then(Answer::new)
+					.unwrap_or(answer)
+			};
+			Solution {
+				answer1: unshuffle(0, submission.answer1),
+				answer2: unshuffle(1, submission.answer2),
+				answer3: unshuffle(2, submission.answer3),
+				answer4: unshuffle(3, submission.answer4),
+				answer5: unshuffle(4, submission.answer5),
+			}
+		}
+
+		// the number of questions answered with the option, or exactly the options, of the answer key
+		pub fn find_score(
+			submission: Solution,
+			solution: Solution,
+		) -> u8 {
+			submission.answers()
+				.iter()
+				.zip(solution.answers().iter())
+				.filter(|(submitted, correct)| submitted.matches(**correct))
+				.count() as u8
+		}
+
+		// the attempt of both attempt calls, a rejection is logged and only charged the weight of its
+		// reads whichever call it came through
+		pub fn submit_attempt(
+			sender: T::AccountId,
+			quiz_count: u64,
+			submission: Solution,
+			referrer: Option<T::AccountId>,
+		) -> Result<AttemptReceipt<BalanceOf<T>>, DispatchErrorWithPostInfo> {
+			Self::do_attempt_quiz(sender.clone(), quiz_count, submission, referrer).map_err(|error| {
+				log::debug!(target: LOG_TARGET, "attempt of quiz {} by {:?} rejected: {:?}", quiz_count, sender, error.error);
+				error
+			})
+		}
+
+		// checks, scores, charges and records an attempt, returning what it cost and how the player did.
+		// Reserves, fees and referral cuts are moved before the owner's share or the stake can still
+		// fail, so the whole attempt is rolled back on any error, whichever entry point it came from
+		#[transactional]
+		pub fn do_attempt_quiz(
+			sender: T::AccountId,
+			quiz_count: u64,
+			submission: Solution,
+			referrer: Option<T::AccountId>,
+		) -> Result<AttemptReceipt<BalanceOf<T>>, DispatchErrorWithPostInfo> {
+			// rejections before scoring only pay for the reads they did
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id)
+				.ok_or(<Error<T>>::QuizDoesNotExist.with_weight(Self::rejected_attempt_weight()))?;
+
+			// read once, the rating gates the attempt and is the base of the update after scoring.
+			// `None` for a player who was never rated, which is not the same as being rated 0
+			let user_rating = <UserRating<T>>::try_get(&sender).ok();
+			let start = Self::get_attempt_in_progress(&quiz_id, &sender);
+			let is_stretch = Self::ensure_can_attempt(&quiz_id, &quiz, &sender, user_rating, &submission, referrer.as_ref(), start.is_some())
+				.map_err(|error| error.with_weight(Self::rejected_attempt_weight()))?;
+			let (questions, solution) = Self::attempt_questions(&quiz_id, &sender, &quiz, start.is_some())
+				.map_err(|error| error.with_weight(Self::rejected_attempt_weight()))?;
+
+			// a started attempt is finalized by this submission
+			if start.is_some() {
+				<AttemptsInProgress<T>>::remove(&quiz_id, &sender);
+				Self::end_attempt_in_progress(&quiz_id);
+			}
+			let seed = start.as_ref().map(Self::attempt_seed);
+			let elapsed = start.as_ref()
+				.map(|start| <frame_system::Pallet<T>>::block_number().saturating_sub(start.started_at));
+			let (correct, precise_score, correct_mask) = Self::score_submission(&quiz_id, &questions, &sender, seed, submission, solution);
+			// partial credit is kept for the stake, strikes, rating and ranking, events and results
+			// show the score rounded to points
+			let score = rating::to_points(precise_score);
+
+			// the reserve of a started attempt is released before the real cost is charged
+			if let Some(start) = start {
+				T::Currency::unreserve(&sender, start.reserved);
+			}
+
+			//the money feature 
+			let token_to_pay = Self::charge_for(Self::charged_correct(correct, questions.len()), is_stretch, quiz.rating);
+			// the flat fee is due on top of whatever the attempt costs, so both are checked together
+			let fee = T::AttemptFee::get();
+			Self::ensure_affordable(T::Currency::free_balance(&sender), Self::at_risk(&quiz, token_to_pay).saturating_add(fee))?;
+			// the referral cut is settled before the fee is taken, a cut the referrer cannot receive
+			// stays with the owner
+			let referral = referrer
+				.map(|referrer| {
+					let cut = Self::referral_cut(&referrer, token_to_pay);
+					(referrer, cut)
+				})
+				.filter(|(_, cut)| !cut.is_zero() && quiz.stake.is_zero());
+			Self::charge_attempt_fee(&sender, fee)?;
+
+			let mut charged = fee;
+			// only what the owner received is paid back should the quiz turn out defective
+			let mut owner_received = BalanceOf::<T>::zero();
+			if quiz.stake.is_zero() {
+				// a referrer takes their cut out of the charge, the owner gets the rest
+				let mut owner_share = token_to_pay;
+				if let Some((referrer, cut)) = referral {
+					Self::transfer_tokens_to_owner(quiz_count, &sender, &referrer, cut)?;
+					owner_share = token_to_pay.saturating_sub(cut);
+				}
+				Self::transfer_tokens_to_owner(quiz_count, &sender, &quiz.owner, owner_share)?;
+				T::Currency::reserve(&quiz.owner, owner_share).map_err(|_| <Error<T>>::InsufficientBalance)?;
+				Self::lock_earnings(&quiz.owner, owner_share);
+				charged = charged.saturating_add(token_to_pay);
+				owner_received = owner_share;
+			} else {
+				let passed = Self::settle_stake(&sender, &quiz, precise_score)?;
+				if !passed {
+					charged = charged.saturating_add(quiz.stake);
+					owner_received = quiz.stake;
+				}
+				Self::deposit_event(Event::StakeSettled(quiz_count, sender.clone(), quiz.stake, passed));
+			}
+			if !owner_received.is_zero() {
+				<AttemptCharges<T>>::mutate(&quiz_id, &sender, |total| *total = total.saturating_add(owner_received));
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			<CooldownUntil<T>>::insert(&sender, now.saturating_add(T::AttemptCooldown::get()));
+			<UserAttempts<T>>::mutate(&sender, |count| *count = count.saturating_add(1));
+			let window = Self::daily_window(now);
+			<DailyAttempts<T>>::mutate(&sender, |(last_window, count)| {
+				if *last_window != window {
+					*last_window = window;
+					*count = 0;
+				}
+				*count = count.saturating_add(1);
+			});
+			<QuizAttempts<T>>::mutate(&quiz_id, &sender, |count| *count = count.saturating_add(1));
+			if precise_score < rating::from_points(T::StrikeScoreThreshold::get()) {
+				<Strikes<T>>::mutate(&quiz_id, &sender, |strikes| *strikes = strikes.saturating_add(1));
+			} else {
+				<Strikes<T>>::remove(&quiz_id, &sender);
+			}
+			let new_rating = Self::update_rating(sender.clone(), Self::rated_score(precise_score, elapsed), user_rating);
+
+			let attempts = <AttemptCount<T>>::mutate(&quiz_id, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+			<QuizAnswerTotals<T>>::mutate(&quiz_id, |totals| {
+				totals.correct = totals.correct.saturating_add(correct as u64);
+				totals.asked = totals.asked.saturating_add(questions.len() as u64);
+			});
+			<AttemptResults<T>>::insert(&quiz_id, &sender, AttemptResult::<T> {
+				score,
+				correct,
+				version: quiz.version,
+				attempted_at: now,
+				elapsed,
+			});
+			if Self::get_best_score(&quiz_id, &sender).map_or(true, |best| precise_score > best.score) {
+				<BestScores<T>>::insert(&quiz_id, &sender, BestScore::<T> { score: precise_score, achieved_at: now });
+				Self::update_tournament_leaders(&quiz_id, &sender, BestScore::<T> { score: precise_score, achieved_at: now });
+			}
+			T::OnQuizCompleted::on_completed(&sender, quiz_count, score);
+			let max_score = questions.len() as u8;
+			if score == max_score {
+				T::OnPerfectScore::on_perfect_score(&sender, quiz_count);
+			}
+			log::debug!(target: LOG_TARGET, "quiz {} attempted by {:?}: score {}, {} correct", quiz_count, sender, score, correct);
+			Self::deposit_event(Event::QuizScore(quiz_count, sender.clone(), score, correct, attempts));
+			Self::deposit_event(Event::AnswerFeedback(quiz_count, sender.clone(), correct_mask));
+			Ok(AttemptReceipt {
+				score,
+				max_score,
+				charged,
+				new_rating,
+			})
+		}
+
+		// the checks a player has to pass before a submission is scored, returning whether the
+		// attempt is a stretch above the player's rating. Shared by `attempt_quiz` and
+		// `dry_run_attempt`, so the dry run rejects exactly what the attempt would
+		pub fn ensure_can_attempt(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+			who: &T::AccountId,
+			user_rating: Option<u32>,
+			submission: &Solution,
+			referrer: Option<&T::AccountId>,
+			started: bool,
+		) -> Result<bool, Error<T>> {
+			ensure!(referrer != Some(who), <Error<T>>::SelfReferral);
+			let is_stretch = Self::ensure_eligible(quiz_id, quiz, who, user_rating, started)?;
+
+			// a submission is only scored against the quiz it was made for, answering none of the
+			// questions an attempt is not asked
+			let asked = Self::questions_per_attempt(quiz_id, quiz);
+			ensure!(
+				submission.answer_count() >= asked && submission.answers()[asked..].iter().all(|answer| answer.options() == 0),
+				<Error<T>>::AnswerCountMismatch
+			);
+			Ok(is_stretch)
+		}
+
+		// the number of questions an attempt is asked and scored out of, the draw of a pooled quiz
+		// and every question of the quiz otherwise
+		pub fn questions_per_attempt(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+		) -> usize {
+			Self::get_pool_draw(quiz_id).map_or(quiz.questions.len(), |draw| draw as usize)
+		}
+
+		// the right answers an attempt is charged as, the questions a pooled quiz did not draw cost
+		// nothing and count as answered right
+		pub fn charged_correct(
+			correct: u8,
+			asked: usize,
+		) -> u8 {
+			correct.saturating_add(QUESTIONS_PER_QUIZ.saturating_sub(asked) as u8)
+		}
+
+		// whether `who` may attempt the quiz at all, returning whether it is a stretch. Also checked
+		// by `start_attempt`, so starting an attempt is refused for everything a submission would be
+		pub fn ensure_eligible(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+			who: &T::AccountId,
+			user_rating: Option<u32>,
+			started: bool,
+		) -> Result<bool, Error<T>> {
+			// past its expiry a quiz only takes the attempts it is kept for
+			ensure!(started || !<ExpiredAt<T>>::contains_key(quiz_id), <Error<T>>::QuizExpiring);
+			ensure!(!<BannedAccounts<T>>::contains_key(who), <Error<T>>::AccountBanned);
+			// ensuring the quiz attemptor is not the quiz creator or one of their known accounts
+			ensure!(who != &quiz.owner, <Error<T>>::OwnerCannotAttemptQuiz);
+			ensure!(!<LinkedAccounts<T>>::contains_key(quiz_id, who), <Error<T>>::LinkedAccountBlocked);
+			ensure!(
+				!Self::is_monetized(quiz) || !<MonetizationOptOut<T>>::contains_key(who),
+				<Error<T>>::MonetizationOptedOut
+			);
+			ensure!(<frame_system::Pallet<T>>::block_number() >= Self::get_cooldown_until(who), <Error<T>>::CooldownActive);
+			ensure!(Self::within_daily_quota(who), <Error<T>>::DailyQuotaExceeded);
+			// repeated poor attempts look like brute forcing the answer key
+			let max_strikes = T::MaxWrongAttempts::get();
+			ensure!(max_strikes == 0 || Self::get_strikes(quiz_id, who) < max_strikes, <Error<T>>::AttemptLimitReached);
+			ensure!(
+				quiz.max_attempts_per_user == 0 || Self::get_quiz_attempts(quiz_id, who) < quiz.max_attempts_per_user,
+				<Error<T>>::AttemptLimitReached
+			);
+			// a player who knows the answers of the original would trivially ace its copy
+			ensure!(
+				Self::get_quiz_source(quiz_id).map_or(true, |source| !<AttemptResults<T>>::contains_key(&source, who)),
+				<Error<T>>::AlreadyAttemptedSource
+			);
+
+			let user_rating = Self::eligibility_rating(who, user_rating);
+
+			// ensure the user is qualified to attempt the quiz, players slightly below the requirement
+			// may still attempt it as a stretch at a higher cost
+			let required = rating::min_required(quiz.rating);
+			let is_stretch = user_rating < required;
+			ensure!(rating::within_margin(user_rating, required, T::StretchMargin::get()), <Error<T>>::UserRatingTooLow);
+			Ok(is_stretch)
+		}
+
+		// scores a submission in displayed option order, returning the raw number of correct
+		// answers, the score in hundredths of a point after negative marking and partial credit and
+		// the `correct_mask`
+		pub fn score_submission(
+			quiz_id: &T::Hash,
+			questions: &[Question],
+			who: &T::AccountId,
+			attempt_seed: Option<T::Hash>,
+			submission: Solution,
+			solution: Solution,
+		) -> (u8, u32, u8) {
+			let mut submission = submission;
+			if T::ShuffleOptions::get() {
+				submission = Self::unshuffle_submission(quiz_id, who, attempt_seed, submission);
+			}
+			let answered = Self::count_answered(&submission);
+			let near_misses = Self::find_near_misses(questions, &submission, &solution);
+			let partial_selections = Self::find_partial_selections(questions, &submission, &solution);
+			let correct_mask = Self::correct_mask(&submission, &solution);
+			let correct = Self::find_score(submission, solution);
+			let score = Self::net_score(correct, answered)
+				.saturating_add(T::OrdinalPartialCredit::get() * rating::from_points(near_misses))
+				.saturating_add(T::MultiSelectPartialCredit::get() * rating::from_points(partial_selections))
+				.min(rating::from_points(questions.len() as u8));
+			(correct, score, correct_mask)
+		}
+
+		// bit `i` is set when question `i` was answered right. Which questions were missed is all a
+		// player learns, never the right option itself
+		pub fn correct_mask(
+			submission: &Solution,
+			solution: &Solution,
+		) -> u8 {
+			submission.answers()
+				.iter()
+				.zip(solution.answers().iter())
+				.enumerate()
+				.filter(|(_, (submitted, correct))| submitted.matches(**correct))
+				.fold(0u8, |mask, (question, _)| mask | 1 << question)
+		}
+
+		// the questions an attempt is scored against with their answer key, on quizzes with a
+		// question pool these are the ones drawn when the attempt was started
+		pub fn attempt_questions(
+			quiz_id: &T::Hash,
+			who: &T::AccountId,
+			quiz: &Quiz<T>,
+			started: bool,
+		) -> Result<(Vec<Question>, Solution), Error<T>> {
+			let pool = match Self::get_question_pool(quiz_id) {
+				Some(pool) => pool,
+				None => {
+					let solution = Self::get_solution(quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+					return Ok((quiz.questions.clone(), solution));
+				}
+			};
+
+			// pooled questions are only known once drawn, which happens when an attempt starts
+			ensure!(started, <Error<T>>::NoAttemptInProgress);
+			let drawn = Self::get_drawn_questions(quiz_id, who).ok_or(<Error<T>>::NoAttemptInProgress)?;
+			let mut questions = Vec::new();
+			let mut answers = [Answer::Abstain; QUESTIONS_PER_QUIZ];
+			for (position, index) in drawn.iter().enumerate() {
+				let (question, answer) = pool.get(*index as usize).cloned().ok_or(<Error<T>>::QuizDoesNotExist)?;
+				questions.push(question);
+				if let Some(slot) = answers.get_mut(position) {
+					*slot = answer;
+				}
+			}
+			Ok((questions, Solution::from_answers(answers)))
+		}
+
+		// picks `draw` distinct pool indices for an attempt, a partial Fisher-Yates driven by
+		// a hash of the attempt seed per step
+		pub fn draw_questions(
+			quiz_id: &T::Hash,
+			who: &T::AccountId,
+			seed: T::Hash,
+			pool_size: u32,
+			draw: usize,
+		) -> Vec<u32> {
+			let mut indices: Vec<u32> = (0..pool_size).collect();
+			let draw = draw.min(indices.len());
+			for i in 0..draw {
+				let step = T::Hashing::hash_of(&(quiz_id, who, seed, i as u32));
+				let j = i + Self::random_below(step, indices.len() - i);
+				indices.swap(i, j);
+			}
+			indices.truncate(draw);
+			indices
+		}
+
+		// runs every check and the scoring of `attempt_quiz` without writing or transferring
+		// anything, returning the score the attempt would get or why it would be rejected
+		pub fn dry_run_attempt(
+			quiz_count: u64,
+			who: &T::AccountId,
+			submission: Solution,
+			referrer: Option<T::AccountId>,
+		) -> Result<u8, AttemptStatus> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id).ok_or(AttemptStatus::QuizDoesNotExist)?;
+			let start = Self::get_attempt_in_progress(&quiz_id, who);
+			let user_rating = <UserRating<T>>::try_get(who).ok();
+			let is_stretch = Self::ensure_can_attempt(&quiz_id, &quiz, who, user_rating, &submission, referrer.as_ref(), start.is_some())?;
+			let (questions, solution) = Self::attempt_questions(&quiz_id, who, &quiz, start.is_some())?;
+			let seed = start.as_ref().map(Self::attempt_seed);
+			let (correct, precise_score, _) = Self::score_submission(&quiz_id, &questions, who, seed, submission, solution);
+
+			// the reserve of a started attempt is released before paying, so it counts as available
+			let reserved = start.map_or_else(Zero::zero, |start| start.reserved);
+			let available = T::Currency::free_balance(who).saturating_add(reserved);
+			let due = Self::at_risk(&quiz, Self::charge_for(Self::charged_correct(correct, questions.len()), is_stretch, quiz.rating))
+				.saturating_add(T::AttemptFee::get());
+			Self::ensure_affordable(available, due)?;
+			Ok(rating::to_points(precise_score))
+		}
+
+		// checks that `available` pays `due` and leaves the existential deposit, payments are made
+		// with `KeepAlive` and would otherwise fail as an opaque `InsufficientBalance`
+		pub fn ensure_affordable(
+			available: BalanceOf<T>,
+			due: BalanceOf<T>,
+		) -> Result<(), Error<T>> {
+			ensure!(available >= due, <Error<T>>::InsufficientBalance);
+			ensure!(available.saturating_sub(due) >= T::Currency::minimum_balance(), <Error<T>>::WouldReapAccount);
+			Ok(())
+		}
+
+		// the number of answers on ordinal questions that picked an option next to the correct one,
+		// answers on other questions are right or wrong only
+		pub fn find_near_misses(
+			questions: &[Question],
+			submission: &Solution,
+			solution: &Solution,
+		) -> u8 {
+			questions.iter()
+				.zip(submission.answers().iter().zip(solution.answers().iter()))
+				.filter(|(question, (submitted, correct))| {
+					match (submitted.index(), correct.index()) {
+						(Some(submitted), Some(correct)) => question.ordinal && submitted.max(correct) - submitted.min(correct) == 1,
+						_ => false,
+					}
+				})
+				.count() as u8
+		}
+
+		// the number of answers on multi-select questions that picked some of the correct options
+		// and nothing else, but not all of them
+		pub fn find_partial_selections(
+			questions: &[Question],
+			submission: &Solution,
+			solution: &Solution,
+		) -> u8 {
+			questions.iter()
+				.zip(submission.answers().iter().zip(solution.answers().iter()))
+				.filter(|(question, (submitted, correct))| {
+					let (submitted, correct) = (submitted.options(), correct.options());
+					question.multi_select && submitted != 0 && submitted != correct && submitted & !correct == 0
+				})
+				.count() as u8
+		}
+
+		// the number of answers of a submission that pick an existing option
+		pub fn count_answered(
+			submission: &Solution,
+		) -> u8 {
+			submission.answers()
+				.iter()
+				.filter(|answer| answer.options() != 0)
+				.count() as u8
+		}
+
+		// the score in hundredths of a point after negative marking, every wrong answer costs
+		// `T::WrongAnswerPenalty` of a point and the result is clamped at zero
+		pub fn net_score(
+			correct: u8,
+			answered: u8,
+		) -> u32 {
+			let wrong = answered.saturating_sub(correct);
+			rating::from_points(correct).saturating_sub(T::WrongAnswerPenalty::get() * rating::from_points(wrong))
+		}
+
+		// the rating a player is gated by, a provisional player with fewer than
+		// `T::MinAttemptsForRating` attempts is treated as unrated so one lucky score opens nothing
+		pub fn eligibility_rating(
+			who: &T::AccountId,
+			user_rating: Option<u32>,
+		) -> u8 {
+			if Self::get_user_attempts(who) < T::MinAttemptsForRating::get() {
+				0
+			} else {
+				user_rating.map_or(0, rating::to_points)
+			}
+		}
+
+		// function to update the rating of the user, `user_rating` is the rating before the attempt
+		// and the new rating in points is returned
+		pub fn update_rating(
+			user: T::AccountId,
+			current_score: u32,
+			user_rating: Option<u32>,
+		) -> u8 {
+			// function body starts here
+			let old_rating = user_rating.map_or(0, rating::to_points);
+			let new_rating = Self::next_rating(user_rating, current_score);
+			Self::set_user_rating(&user, new_rating, user_rating);
+			let user_rating = rating::to_points(new_rating);
+			<ScoreHistory<T>>::mutate(&user, |history| {
+				history.push(current_score);
+				if history.len() > MAX_SCORE_HISTORY {
+					history.remove(0);
+				}
+			});
+			// improving is rewarded with being allowed to try again right away
+			if T::ResetCooldownOnImprovement::get() && user_rating > old_rating {
+				<CooldownUntil<T>>::remove(&user);
+			}
+			Self::emit_milestones(&user, old_rating, user_rating);
+			Self::update_leaderboard(user, user_rating);
+			// function body ends here
+			user_rating
+		}
+
+		// the score a rating moves towards, raised by up to `T::SpeedBonus` of the score for fast
+		// submissions of started attempts. Attempts submitted `T::SpeedBonusWindow` or more blocks
+		// after their start, and attempts that were not started, get no bonus
+		pub fn rated_score(
+			score: u32,
+			elapsed: Option<T::BlockNumber>,
+		) -> u32 {
+			let window = T::SpeedBonusWindow::get();
+			let elapsed = match elapsed {
+				Some(elapsed) if elapsed < window => elapsed,
+				_ => return score,
 			};
-			let quiz_count = Self::get_latest_quiz() + 1;
-			let quiz_id = T::Hashing::hash_of(&quiz_count);
-			<Quizzes<T>>::insert(quiz_id.clone(), quiz);
-			<Solutions<T>>::insert(quiz_id, solution);
-			<QuizCnt<T>>::put(quiz_count);
+			let window: u32 = window.saturated_into::<u32>();
+			let remaining = Perbill::from_rational(window.saturating_sub(elapsed.saturated_into::<u32>()), window);
+			score.saturating_add((T::SpeedBonus::get() * remaining) * score)
+		}
 
-			let the_end_block_number = <frame_system::Pallet<T>>::block_number();
-			Self::add_quiz_to_be_deleted(the_end_block_number, quiz_count)?;
-			Self::deposit_event(Event::QuizCreated(quiz_count, sender, rating));
-			Ok(())
+		// the rating formula, the stored rating after scoring `score` with the given rating. A player
+		// who was never rated is seeded with their first score, after that the rating is an
+		// exponential moving average `old + alpha * (score - old)` of the scores, kept in hundredths
+		// of a point and only rounded to points where it is compared. A rating of 0 is averaged like
+		// any other, so one good score cannot restore a dropped rating. The score is capped at
+		// `T::MaxRating`, so the rating never leaves the range quiz ratings live in
+		pub fn next_rating(
+			user_rating: Option<u32>,
+			score: u32,
+		) -> u32 {
+			match user_rating {
+				None => score.min(rating::from_points(T::MaxRating::get())),
+				Some(user_rating) => rating::rating_average(user_rating, score, T::RatingAlpha::get(), T::MaxRating::get()),
+			}
 		}
 
-		#[pallet::weight(100)]
-		pub fn attempt_quiz(
-			origin: OriginFor<T>,
-			quiz_count: u64,
-			submission: Solution
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			
-			let quiz_id = T::Hashing::hash_of(&quiz_count);
-			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+		// replays the kept score history of a player through the current rating formula. Only the
+		// latest `MAX_SCORE_HISTORY` scores are kept, older ones barely move the rating anyway
+		pub fn recompute_rating(
+			who: &T::AccountId,
+		) -> Option<u32> {
+			Self::get_score_history(who)
+				.into_iter()
+				.fold(None, |rating, score| Some(Self::next_rating(rating, score)))
+		}
 
-			// ensuring the quiz attemptor is not the quiz creator
-			ensure!(sender != quiz.owner,<Error<T>>::OwnerCannotAttemptQuiz);
+		// stores a player's rating, keeping the per point counts used for ranking in step. `old` is
+		// the stored rating it replaces as already read by the caller, `None` for a player never
+		// rated, so the rating is not read again
+		pub fn set_user_rating(
+			user: &T::AccountId,
+			rating: u32,
+			old: Option<u32>,
+		){
+			if let Some(old) = old {
+				<RatingCounts<T>>::mutate(rating::to_points(old), |count| *count = count.saturating_sub(1));
+			}
+			<RatingCounts<T>>::mutate(rating::to_points(rating), |count| *count = count.saturating_add(1));
+			<UserRating<T>>::insert(user, rating);
+		}
 
-			let user_rating = Self::get_user_rating(&sender);
+		// the rating of a player in points, 0 for a player never rated
+		pub fn get_user_rating(
+			who: &T::AccountId,
+		) -> u8 {
+			rating::to_points(Self::get_user_rating_scaled(who))
+		}
 
-			// ensure the user is qualified to attempt the quiz
-			ensure!(user_rating >= quiz.rating - 1,<Error<T>>::UserRatingTooLow);
+		// the 1-based rank of a player by rating among all rated players, players sharing a rating
+		// share a rank. Reads one count per rating above the player's, so at most 255 reads
+		pub fn user_rank(
+			who: &T::AccountId,
+		) -> Option<u32> {
+			if !<UserRating<T>>::contains_key(who) {
+				return None;
+			}
+			let rating = Self::get_user_rating(who);
+			let above = (rating as u16 + 1..=u8::MAX as u16)
+				.map(|higher| Self::get_rating_count(higher as u8))
+				.fold(0u32, |total, count| total.saturating_add(count));
+			Some(above.saturating_add(1))
+		}
 
-			let solution = Self::get_solution(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+		// reports every multiple of `T::RatingMilestoneStep` a rating rose to or past, a jump over
+		// several of them reports each one
+		pub fn emit_milestones(
+			user: &T::AccountId,
+			old_rating: u8,
+			new_rating: u8,
+		){
+			for milestone in rating::milestones_between(old_rating, new_rating, T::RatingMilestoneStep::get()) {
+				Self::deposit_event(Event::RatingMilestone(user.clone(), milestone));
+			}
+		}
 
-			let score = Self::find_score(submission, solution);
+		// places a player on the leaderboard with their new rating. Ties are broken by the block
+		// the rating was reached and then by account, so the order only depends on the entries and
+		// never on the order they were inserted in
+		pub fn update_leaderboard(
+			user: T::AccountId,
+			rating: u8,
+		){
+			<Leaderboard<T>>::mutate(|board| {
+				let now = <frame_system::Pallet<T>>::block_number();
+				let achieved_at = match board.iter().find(|entry| entry.account == user) {
+					Some(entry) if entry.rating == rating => entry.achieved_at,
+					_ => now,
+				};
+				board.retain(|entry| entry.account != user);
+				board.push(LeaderboardEntry::<T> { account: user, rating, achieved_at });
+				board.sort_by(|a, b| {
+					b.rating.cmp(&a.rating)
+						.then_with(|| a.achieved_at.cmp(&b.achieved_at))
+						.then_with(|| a.account.cmp(&b.account))
+				});
+				board.truncate(LEADERBOARD_SIZE);
+			});
+		}
 
-			let user_rating = Self::get_user_rating(&sender);
+		// removes the quizzes due by `now` as long as their work fits in `budget`, returning the
+		// weight consumed. Blocks are walked from `NextExpiryBlock` on and every bucket and quiz is
+		// only read once the budget has room for it, what is left stays scheduled where it is and
+		// is picked up by the next block. A budget too small for a single quiz defers every expiry
+		pub fn check_and_delete_quiz(
+			now : T::BlockNumber,
+			budget : Weight,
+		) -> Weight {
+			// function body starts here
+			let db = T::DbWeight::get();
+			let mut consumed = db.reads_writes(1, 1);
+			if consumed > budget {
+				return 0;
+			}
+			let mut block_number = Self::get_next_expiry_block().unwrap_or(now);
+			let next_block = now.saturating_add(1u32.into());
+			let (mut removed, mut deferred) = (0u32, 0u32);
+			'blocks: while block_number <= now {
+				let block_hash = T::Hashing::hash_of(&block_number.saturated_into::<u64>());
+				for bucket in 0..DELETION_BUCKETS {
+					// the bucket is read and then removed or written back with what is left
+					if consumed.saturating_add(db.reads_writes(1, 1)) > budget {
+						break 'blocks;
+					}
+					consumed = consumed.saturating_add(db.reads_writes(1, 1));
+					let mut due = Self::get_quiz_to_delete(&block_hash, bucket).into_inner();
+					// taken from the back, what is left over only shrank and still fits its bucket
+					while let Some(hash) = due.last().cloned() {
+						// the quiz and the count of its attempts in progress
+						if consumed.saturating_add(db.reads(2)) > budget {
+							Self::keep_scheduled(&block_hash, bucket, due);
+							break 'blocks;
+						}
+						consumed = consumed.saturating_add(db.reads(2));
+						let quiz = match Self::get_quiz(&hash) {
+							Some(quiz) => quiz,
+							None => {
+								due.pop();
+								continue;
+							},
+						};
+						// the grace check reads every attempt in progress, then the quiz is either
+						// expired or scheduled again
+						let in_progress = Self::get_attempts_in_progress_count(&hash);
+						let grace_check = db.reads_writes((in_progress as Weight).saturating_add(1), 1);
+						let outcome = Self::expire_quiz_weight().max(Self::schedule_weight().saturating_add(db.writes(1)));
+						if consumed.saturating_add(grace_check).saturating_add(outcome) > budget {
+							Self::keep_scheduled(&block_hash, bucket, due);
+							break 'blocks;
+						}
+						due.pop();
+						consumed = consumed.saturating_add(grace_check);
+						if Self::in_expiry_grace(&hash, block_number, now, in_progress) {
+							// checked again every block, so it goes as soon as the last attempt is submitted
+							Self::defer_expiry(&hash, quiz, next_block);
+							consumed = consumed.saturating_add(Self::schedule_weight()).saturating_add(db.writes(1));
+							deferred += 1;
+							continue;
+						}
+						// the answer key stays readable once the quiz is closed
+						if let Some(solution) = Self::get_solution(&hash) {
+							// an archived quiz is out of `Quizzes` like any expired one, so it cannot be attempted
+							if T::ArchiveExpiredQuizzes::get() {
+								<ArchivedQuizzes<T>>::insert(&hash, (quiz.clone(), solution.clone()));
+							}
+							<RevealedSolutions<T>>::insert(&hash, solution);
+						}
+						Self::remove_quiz(&hash, &quiz);
+						Self::deposit_event(Event::QuizExpired(quiz.count, now));
+						consumed = consumed.saturating_add(Self::expire_quiz_weight());
+						removed += 1;
+					}
+					<QuizToDelete<T>>::remove(&block_hash, bucket);
+				}
+				block_number = block_number.saturating_add(1u32.into());
+			}
+			<NextExpiryBlock<T>>::put(block_number);
+			if removed > 0 || deferred > 0 || block_number <= now {
+				log::info!(target: LOG_TARGET, "block {:?}: {} quizzes expired, {} deferred, schedule left at block {:?}", now, removed, deferred, block_number);
+			}
+			consumed
+			//function body ends here
+		}
 
-			//the money feature 
-			let token_to_pay = (5 - score as u32) * T::TokensPerQuestion::get();
-			let token_to_pay : BalanceOf<T> = token_to_pay.into();
-			Self::transfer_tokens_to_owner(&sender, &quiz.owner, token_to_pay)?;			
-			Self::update_rating(sender.clone(), score.clone(), user_rating);
+		// writes back the quizzes of a bucket the expiry budget did not reach
+		pub fn keep_scheduled(
+			block_hash: &T::Hash,
+			bucket: u8,
+			due: Vec<T::Hash>,
+		){
+			if let Ok(left) = DeletionBucketOf::<T>::try_from(due) {
+				<QuizToDelete<T>>::insert(block_hash, bucket, left);
+			}
+		}
 
-			Self::deposit_event(Event::QuizScore(quiz_count, sender, score));
-			Ok(())
+		// whether a quiz due since `expired_at` is kept for an attempt started before it expired
+		// that is still within `T::ExpiryGracePeriod` at `now`. At most `in_progress` attempts, the
+		// count the weight was charged for, are looked at
+		pub fn in_expiry_grace(
+			quiz_id: &T::Hash,
+			expired_at: T::BlockNumber,
+			now: T::BlockNumber,
+			in_progress: u32,
+		) -> bool {
+			let grace = T::ExpiryGracePeriod::get();
+			if grace.is_zero() {
+				return false;
+			}
+			// new attempts are refused from the original expiry on, so later ones never extend it
+			let expired_at = Self::get_expired_at(quiz_id).unwrap_or(expired_at);
+			let pending = <AttemptsInProgress<T>>::iter_prefix_values(quiz_id)
+				.take(in_progress as usize)
+				.any(|start| start.started_at <= expired_at && start.started_at.saturating_add(grace) > now);
+			if pending {
+				<ExpiredAt<T>>::insert(quiz_id, expired_at);
+			}
+			pending
 		}
 
-		#[pallet::weight(100)]
-		pub fn delete_quiz(
-			origin: OriginFor<T>,
-			quiz_count: u64
-		) -> DispatchResult {
-			// function body starts here
-			let sender = ensure_signed(origin)?;
-			let quiz_id = T::Hashing::hash_of(&quiz_count);
-			let quiz = Self::get_quiz(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)?;
+		// frees the slot of a submitted or cancelled attempt. Attempts of a removed quiz can still be
+		// cancelled, the count went with the quiz then
+		pub fn end_attempt_in_progress(
+			quiz_id: &T::Hash,
+		){
+			if <Quizzes<T>>::contains_key(quiz_id) {
+				<AttemptsInProgressCount<T>>::mutate(quiz_id, |count| *count = count.saturating_sub(1));
+			}
+		}
 
-			// ensuring that only the quiz owner can set the quiz for deletion
-			ensure!(sender == quiz.owner, <Error<T>>::NotTheQuizOwner);
+		// reschedules the deletion of a quiz that did not fit in its block's budget. A quiz the full
+		// schedule has no room for is left to `force_expire_quiz`
+		pub fn defer_expiry(
+			quiz_id: &T::Hash,
+			mut quiz: Quiz<T>,
+			expires_at: T::BlockNumber,
+		){
+			match Self::schedule_deletion(quiz_id, expires_at) {
+				Ok(scheduled) => {
+					quiz.expires_at = scheduled;
+					<Quizzes<T>>::insert(quiz_id, quiz);
+				},
+				Err(_) => log::warn!(target: LOG_TARGET, "quiz {} dropped from the deletion schedule, every bucket from block {:?} on is full", quiz.count, expires_at),
+			}
+		}
+
+		// removes a quiz and everything stored alongside it, the deletion schedule is left to the caller
+		pub fn remove_quiz(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+		){
+			// the prize pool is paid out before the scores it is split by are cleared. The entries
+			// kept per player are unbounded and cleared over the next blocks by `continue_cleanup`,
+			// ids are never reused so nothing can mix with them meanwhile
+			Self::settle_tournament(quiz_id, quiz);
+			<PendingCleanup<T>>::insert(quiz_id, ());
+			<TournamentLeaders<T>>::remove(quiz_id);
 			<Quizzes<T>>::remove(quiz_id);
-			Ok(())
-			// function body ends here
+			<QuizByIndex<T>>::remove(quiz.count);
+			<Solutions<T>>::remove(quiz_id);
+			<Explanations<T>>::remove(quiz_id);
+			<AttemptCount<T>>::remove(quiz_id);
+			<QuizAnswerTotals<T>>::remove(quiz_id);
+			<QuestionPools<T>>::remove(quiz_id);
+			<PoolDraws<T>>::remove(quiz_id);
+			<QuizSources<T>>::remove(quiz_id);
+			<ExpiredAt<T>>::remove(quiz_id);
+			<AttemptsInProgressCount<T>>::remove(quiz_id);
+			<QuizQuality<T>>::remove(quiz_id);
+			<OwnedQuizzes<T>>::remove(&quiz.owner, quiz_id);
+			<OwnedQuizCount<T>>::mutate(&quiz.owner, |count| *count = count.saturating_sub(1));
+			Self::release_deposit(quiz_id, quiz);
 		}
-    }
 
-	impl<T:Config> Pallet<T> {
-		//Helper functions here
+		// clears at most `limit` per-player entries of a removed quiz, returning whether none are
+		// left and how many were cleared. The charges of a defective quiz outlive it until everyone
+		// is refunded, `process_refunds` clears those
+		pub fn clear_quiz_entries(
+			quiz_id: &T::Hash,
+			limit: u32,
+		) -> (bool, u32) {
+			let mut remaining = limit;
+			let mut done = true;
+			done &= Self::tally_removed(<BestScores<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<DrawnQuestions<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<CancelledSeeds<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<Strikes<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<QuizAttempts<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			if !<PendingRefunds<T>>::contains_key(quiz_id) {
+				done &= Self::tally_removed(<AttemptCharges<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			}
+			done &= Self::tally_removed(<QuizTranslations<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<LinkedAccounts<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<AttemptResults<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			done &= Self::tally_removed(<StarsGiven<T>>::remove_prefix(quiz_id, Some(remaining)), &mut remaining);
+			(done, limit.saturating_sub(remaining))
+		}
 
-		pub fn find_score(
-			submission: Solution,
-			solution: Solution,
-		) -> u8 {
-			// function body starts here
+		// takes the removed keys of a limited prefix removal off `remaining`, returning whether the
+		// prefix is empty now
+		pub fn tally_removed(
+			result: KillStorageResult,
+			remaining: &mut u32,
+		) -> bool {
+			match result {
+				KillStorageResult::AllRemoved(removed) => {
+					*remaining = remaining.saturating_sub(removed);
+					true
+				},
+				KillStorageResult::SomeRemaining(removed) => {
+					*remaining = remaining.saturating_sub(removed);
+					false
+				},
+			}
+		}
+
+		// clears per-player entries of removed quizzes as long as they fit in `budget`, returning
+		// the weight consumed
+		pub fn continue_cleanup(
+			budget: Weight,
+		) -> Weight {
+			let per_entry = T::DbWeight::get().writes(1);
+			// every quiz is looked up and its mark removed once its entries are all cleared
+			let per_quiz = T::DbWeight::get().reads(1).saturating_add(per_entry);
+			let mut consumed = T::DbWeight::get().reads(1);
+			// nothing is read when the budget has no room for a single quiz
+			if per_entry == 0 || consumed.saturating_add(per_quiz) > budget {
+				return 0;
+			}
+			let mut cleared_total : u32 = 0;
+			let most = (budget.saturating_sub(consumed) / per_quiz).min(MAX_CLEANUP_PER_BLOCK as Weight);
+			// collected first so the map is not modified while iterating it
+			let pending : Vec<T::Hash> = <PendingCleanup<T>>::iter_keys().take(most as usize).collect();
+			for quiz_id in pending {
+				if consumed.saturating_add(per_quiz) > budget || cleared_total >= MAX_CLEANUP_PER_BLOCK {
+					break;
+				}
+				let limit = (budget.saturating_sub(consumed).saturating_sub(per_quiz) / per_entry)
+					.min(MAX_CLEANUP_PER_BLOCK.saturating_sub(cleared_total) as Weight) as u32;
+				let (done, cleared) = if limit == 0 { (false, 0) } else { Self::clear_quiz_entries(&quiz_id, limit) };
+				cleared_total = cleared_total.saturating_add(cleared);
+				consumed = consumed.saturating_add(T::DbWeight::get().reads(1)).saturating_add(per_entry.saturating_mul(cleared as Weight));
+				if done {
+					<PendingCleanup<T>>::remove(&quiz_id);
+					consumed = consumed.saturating_add(per_entry);
+				}
+			}
+			consumed
+		}
+
+		// the account holding the prize pools
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
 
-			let mut score : u8 = 0;
+		// keeps the `T::TournamentWinners` best scorers of a quiz as attempts come in, so settling
+		// never has to go through every player. Ties are ranked by the block the score was reached
+		// and then by account
+		pub fn update_tournament_leaders(
+			quiz_id: &T::Hash,
+			who: &T::AccountId,
+			best: BestScore<T>,
+		){
+			<TournamentLeaders<T>>::mutate(quiz_id, |leaders| {
+				leaders.retain(|(leader, _)| leader != who);
+				leaders.push((who.clone(), best));
+				leaders.sort_by(|(a, x), (b, y)| {
+					y.score.cmp(&x.score)
+						.then_with(|| x.achieved_at.cmp(&y.achieved_at))
+						.then_with(|| a.cmp(b))
+				});
+				leaders.truncate(T::TournamentWinners::get() as usize);
+			});
+		}
 
-			// checking for correct answers
-			if submission.answer1 == solution.answer1 {
-				score+=1;
+		// splits the prize pool of a closing quiz among its tournament leaders in proportion to
+		// their scores. Rounding dust goes to the last winner, a pool nobody competed for goes back
+		// to its funders and whatever cannot be paid is kept in `UnclaimedPrizes`
+		pub fn settle_tournament(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+		){
+			let funders = <PrizeFunders<T>>::take(quiz_id);
+			let pool = <PrizePools<T>>::take(quiz_id);
+			if pool.is_zero() {
+				return;
 			}
-			if submission.answer2 == solution.answer2 {
-				score+=1;
+			let pallet_account = Self::account_id();
+
+			let ranked = Self::get_tournament_leaders(quiz_id);
+			if ranked.is_empty() {
+				for (funder, funded) in funders {
+					match T::Currency::transfer(&pallet_account, &funder, funded, ExistenceRequirement::AllowDeath) {
+						Ok(()) => Self::deposit_event(Event::PrizeRefunded(quiz.count, funder, funded)),
+						Err(_) => Self::keep_unpaid(quiz.count, funder, funded),
+					}
+				}
+				Self::deposit_event(Event::TournamentSettled(quiz.count, Vec::new()));
+				return;
 			}
-			if submission.answer3 == solution.answer3 {
-				score+=1;
+
+			let winners = ranked.len();
+			let total : u32 = ranked.iter().map(|(_, best)| best.score).sum();
+			let mut remaining = pool;
+			let mut payouts = Vec::new();
+			for (index, (who, best)) in ranked.into_iter().enumerate() {
+				let share = if index + 1 == winners {
+					remaining
+				} else if total == 0 {
+					// nobody scored, the winners split the pool evenly
+					Perbill::from_rational(1u32, winners as u32).mul_floor(pool)
+				} else {
+					Perbill::from_rational(best.score, total).mul_floor(pool)
+				}.min(remaining);
+				remaining = remaining.saturating_sub(share);
+				if Self::pay_prize(quiz.count, &who, share).is_ok() {
+					payouts.push((who, share));
+				} else {
+					Self::keep_unpaid(quiz.count, who, share);
+				}
 			}
-			if submission.answer4 == solution.answer4 {
-				score+=1;
+			Self::deposit_event(Event::TournamentSettled(quiz.count, payouts));
+		}
+
+		// records a prize or refund the pallet account failed to pay, e.g. below the existential
+		// deposit of a new account, for the account to take with `claim_prize`
+		pub fn keep_unpaid(
+			quiz_count: u64,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		){
+			<UnclaimedPrizes<T>>::mutate(&who, |unpaid| *unpaid = unpaid.saturating_add(amount));
+			Self::deposit_event(Event::PrizeUnpaid(quiz_count, who, amount));
+		}
+
+		// pays a prize out of the pallet account, locked in a vesting schedule starting now when it
+		// is above `T::VestingThreshold`. A prize the schedule cannot be added for is left liquid
+		pub fn pay_prize(
+			quiz_count: u64,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::Currency::transfer(&Self::account_id(), who, amount, ExistenceRequirement::AllowDeath)?;
+			if amount <= T::VestingThreshold::get() {
+				return Ok(());
 			}
-			if submission.answer5 == solution.answer5 {
-				score+=1;
+			let blocks : BalanceOf<T> = T::VestingPeriod::get().saturated_into::<u32>().max(1).into();
+			let per_block = (amount / blocks).max(1u32.into());
+			let now = <frame_system::Pallet<T>>::block_number();
+			if T::Vesting::can_add_vesting_schedule(who, amount, per_block, now).is_ok() {
+				// checked right before, adding the schedule cannot fail
+				let _ = T::Vesting::add_vesting_schedule(who, amount, per_block, now);
+				Self::deposit_event(Event::PrizeVested(quiz_count, who.clone(), amount));
+			} else {
+				log::debug!(target: LOG_TARGET, "prize of quiz {} for {:?} paid liquid, it cannot be vested", quiz_count, who);
 			}
-			score
-			//function body ends here
+			Ok(())
 		}
 
-		// function to update the rating of the user
-		pub fn update_rating(
-			user: T::AccountId,
-			current_score: u8,
-			user_rating: u8,
+		// drops a quiz from the deletion schedule of the block it expires in, whichever bucket took it
+		pub fn unschedule_deletion(
+			quiz_id: &T::Hash,
+			expires_at: T::BlockNumber,
 		){
-			// function body starts here
-			let total : u8 = match user_rating {
-				0 => 1,
-				_ => 6
-			};
-			let user_rating = (user_rating * 5 + current_score)/total;
-			<UserRating<T>>::insert(user, user_rating);
-			// function body ends here
+			let block : u64 = expires_at.saturated_into::<u64>();
+			let delete_id = T::Hashing::hash_of(&block);
+			let first = Self::deletion_bucket(quiz_id);
+			for offset in 0..DELETION_BUCKETS {
+				let removed = <QuizToDelete<T>>::try_mutate(&delete_id, (first + offset) % DELETION_BUCKETS, |quiz_vec| {
+					match quiz_vec.iter().position(|hash| hash == quiz_id) {
+						Some(index) => {
+							quiz_vec.remove(index);
+							Ok(())
+						},
+						None => Err(()),
+					}
+				});
+				if removed.is_ok() {
+					return;
+				}
+			}
 		}
 
-		pub fn check_and_delete_quiz(
-			block_number : T::BlockNumber
+		// checks the storage invariants of the pallet, returning the first violation found. FRAME
+		// of this version has no `try_state` hook to run it from, try-runtime builds call it after
+		// every upgrade and every block instead
+		pub fn do_try_state() -> Result<(), &'static str> {
+			let latest = Self::get_latest_quiz();
+			let mut stored : u32 = 0;
+			for (quiz_id, quiz) in <Quizzes<T>>::iter() {
+				stored = stored.saturating_add(1);
+				if !<Solutions<T>>::contains_key(&quiz_id) {
+					return Err("quiz without a solution");
+				}
+				if quiz.count > latest {
+					return Err("quiz count above QuizCnt");
+				}
+				if T::Hashing::hash_of(&quiz.count) != quiz_id {
+					return Err("quiz stored under an id not derived from its count");
+				}
+				if !<OwnedQuizzes<T>>::contains_key(&quiz.owner, &quiz_id) {
+					return Err("quiz missing from its owner's index");
+				}
+			}
+			if stored != Self::get_active_quizzes() {
+				return Err("the counter of Quizzes does not match the stored quizzes");
+			}
+
+			let mut indexed : u32 = 0;
+			for (owner, quiz_id, ()) in <OwnedQuizzes<T>>::iter() {
+				indexed = indexed.saturating_add(1);
+				match Self::get_quiz(&quiz_id) {
+					Some(quiz) if quiz.owner == owner => {},
+					Some(_) => return Err("owner index entry for a quiz of another owner"),
+					None => return Err("owner index entry for a missing quiz"),
+				}
+			}
+			let counted = <OwnedQuizCount<T>>::iter_values().fold(0u32, |total, count| total.saturating_add(count));
+			if indexed != counted {
+				return Err("OwnedQuizCount does not match the owner index");
+			}
+			Ok(())
+		}
+
+		// the number of quizzes currently stored, as kept by the counter of `Quizzes`
+		pub fn get_active_quizzes() -> u32 {
+			<Quizzes<T>>::count()
+		}
+
+		// the deposit covering the storage of a quiz with these questions
+		pub fn quiz_deposit(
+			questions: &[Question],
+		) -> BalanceOf<T> {
+			let bytes: BalanceOf<T> = (questions.encoded_size() as u32).into();
+			T::QuizDeposit::get().saturating_add(T::QuizDepositPerByte::get().saturating_mul(bytes))
+		}
+
+		// reserves or refunds the difference when the questions of a quiz change size, a slashed
+		// deposit stays slashed
+		pub fn adjust_deposit(
+			quiz: &mut Quiz<T>,
+			questions: &[Question],
+		) -> DispatchResult {
+			if quiz.deposit.is_zero() {
+				return Ok(());
+			}
+			let required = Self::quiz_deposit(questions);
+			if required > quiz.deposit {
+				T::Currency::reserve(&quiz.owner, required.saturating_sub(quiz.deposit))
+					.map_err(|_| <Error<T>>::InsufficientBalance)?;
+			} else {
+				T::Currency::unreserve(&quiz.owner, quiz.deposit.saturating_sub(required));
+			}
+			quiz.deposit = required;
+			Ok(())
+		}
+
+		// a call refunding up to `T::MaxRefundsPerCall` players, each a pair of transfers off the owner
+		pub fn refunds_weight() -> Weight {
+			BASE_WEIGHT
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				.saturating_add(T::DbWeight::get().reads_writes(3, 3).saturating_mul(T::MaxRefundsPerCall::get() as Weight))
+		}
+
+		// pays back the next `T::MaxRefundsPerCall` players of a deleted defective quiz what its
+		// owner received from them. Refunds only come out of what is left of this quiz's deposit,
+		// never the owner's other reserves or free balance, whatever it cannot cover is lost. The rest
+		// of the deposit is released with the last refund
+		pub fn process_refunds(
+			quiz_count: u64,
 		){
-			// function body starts here
-			let block : u64 = block_number.saturated_into::<u64>();
-			let block_hash = T::Hashing::hash_of(&block);
-			let delete_vec = Self::get_quiz_to_delete(block_hash);
-			for hash in delete_vec {
-				<Quizzes<T>>::remove(hash);
-				
-				Self::deposit_event(Event::QuizDeleted(block.clone()));
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let (owner, mut deposit) = match Self::get_pending_refund(&quiz_id) {
+				Some(pending) => pending,
+				None => return,
+			};
+			let batch: Vec<(T::AccountId, BalanceOf<T>)> = <AttemptCharges<T>>::drain_prefix(&quiz_id)
+				.take(T::MaxRefundsPerCall::get() as usize)
+				.collect();
+			for (player, charge) in batch {
+				let from_deposit = charge.min(deposit);
+				let missing = T::Currency::repatriate_reserved(&owner, &player, from_deposit, BalanceStatus::Free)
+					.unwrap_or(from_deposit);
+				let refunded = from_deposit.saturating_sub(missing);
+				deposit = deposit.saturating_sub(refunded);
+				Self::deposit_event(Event::AttemptRefunded(quiz_count, player, refunded));
 			}
-			//function body ends here
+			if <AttemptCharges<T>>::iter_prefix(&quiz_id).next().is_none() {
+				T::Currency::unreserve(&owner, deposit);
+				<PendingRefunds<T>>::remove(&quiz_id);
+				Self::deposit_event(Event::RefundsCompleted(quiz_count));
+			} else {
+				<PendingRefunds<T>>::insert(&quiz_id, (owner, deposit));
+			}
+		}
+
+		// refunds whatever is left of the creator's deposit and drops the quiz's reports
+		pub fn release_deposit(
+			quiz_id: &T::Hash,
+			quiz: &Quiz<T>,
+		){
+			T::Currency::unreserve(&quiz.owner, quiz.deposit);
+			let _ = <QuizReports<T>>::remove_prefix(quiz_id, None);
+			<ReportCount<T>>::remove(quiz_id);
 		}
 
 		pub fn add_quiz_to_be_deleted(
 			the_end_block_number : T::BlockNumber,
 			quiz_number : u64,
-		) -> Result<(), Error<T>> {
+		) -> Result<T::BlockNumber, Error<T>> {
 			// hook logic down 
 			let mut  the_end_block_number = the_end_block_number.saturated_into::<u64>();
 			// the_end_block_number = 14400 + the_end_block_number;  // this is for production
 			the_end_block_number = 10 + the_end_block_number; // this is for the test
-			let delete_id = T::Hashing::hash_of(&the_end_block_number);
 			let quiz_id = T::Hashing::hash_of(&quiz_number);
-			<QuizToDelete<T>>::try_mutate(&delete_id, |quiz_vec| { 
-				quiz_vec.push(quiz_id);
-				Ok(())
-			})?;
+			Self::schedule_deletion(&quiz_id, the_end_block_number.saturated_into::<T::BlockNumber>())
+		}
+
+		// puts a quiz into the deletion schedule of the block it expires in, trying its own bucket
+		// first and the others of the block after it. A quiz no bucket of the block has room for
+		// spills into the next block, for at most `DELETION_SPILL_BLOCKS` blocks; returns the block
+		// the quiz is scheduled in
+		pub fn schedule_deletion(
+			quiz_id: &T::Hash,
+			expires_at: T::BlockNumber,
+		) -> Result<T::BlockNumber, Error<T>> {
+			let first = Self::deletion_bucket(quiz_id);
+			let mut block_number = expires_at;
+			for _ in 0..DELETION_SPILL_BLOCKS {
+				let delete_id = T::Hashing::hash_of(&block_number.saturated_into::<u64>());
+				for offset in 0..DELETION_BUCKETS {
+					let added = <QuizToDelete<T>>::try_mutate(&delete_id, (first + offset) % DELETION_BUCKETS, |quiz_vec| quiz_vec.try_push(*quiz_id));
+					if added.is_ok() {
+						return Ok(block_number);
+					}
+				}
+				block_number = block_number.saturating_add(1u32.into());
+			}
+			Err(<Error<T>>::DeletionScheduleFull)
+		}
+
+		// the bucket of a block's deletion schedule a quiz goes into first, so that many quizzes
+		// expiring together do not end up in one huge value
+		pub fn deletion_bucket(
+			quiz_id: &T::Hash,
+		) -> u8 {
+			quiz_id.as_ref()[0] % DELETION_BUCKETS
+		}
+
+		// the average player quality rating of a quiz as a fraction of `MAX_STARS`, `None` if unrated
+		pub fn quality_average(
+			quiz_count: u64,
+		) -> Option<Perbill> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quality = Self::get_quiz_quality(&quiz_id);
+			if quality.count == 0 {
+				return None;
+			}
+			Some(Perbill::from_rational(quality.sum, quality.count.saturating_mul(MAX_STARS as u32)))
+		}
+
+		// the share of answers players got wrong over all attempts of a quiz, `None` before its
+		// first attempt
+		pub fn observed_difficulty(
+			quiz_count: u64,
+		) -> Option<Perbill> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let totals = Self::get_answer_totals(&quiz_id);
+			if Self::get_attempt_count(&quiz_id) == 0 || totals.asked == 0 {
+				return None;
+			}
+			Some(Perbill::from_rational(totals.asked.saturating_sub(totals.correct), totals.asked))
+		}
+
+		// the quizzes with the given counts in the order asked for, `None` for the ones that do not
+		// exist; counts past the first `MAX_QUIZ_LOOKUP` are ignored
+		pub fn get_quizzes(
+			quiz_counts: Vec<u64>,
+		) -> Vec<Option<Quiz<T>>> {
+			quiz_counts.into_iter()
+				.take(MAX_QUIZ_LOOKUP as usize)
+				.map(|quiz_count| Self::get_quiz(&T::Hashing::hash_of(&quiz_count)))
+				.collect()
+		}
+
+		// a page of quizzes in creation order, starting after the given count and holding at most
+		// `limit` (capped at `MAX_QUIZ_PAGE`) quizzes. Deleted counts are skipped but still walked,
+		// so at most `MAX_QUIZ_SCAN` counts are looked at and a page may come back short or empty.
+		// The last count looked at is returned as the `start_after` of the next page, `None` once
+		// the latest quiz was reached
+		pub fn list_quizzes(
+			start_after: Option<u64>,
+			limit: u32,
+		) -> (Vec<(u64, QuizSummary<T>)>, Option<u64>) {
+			let limit = limit.min(MAX_QUIZ_PAGE) as usize;
+			let first = start_after.map_or(1, |count| count.saturating_add(1));
+			let latest = Self::get_latest_quiz();
+			let mut page = Vec::new();
+			let mut last = None;
+			for quiz_count in (first..=latest).take(MAX_QUIZ_SCAN as usize) {
+				if page.len() >= limit {
+					break;
+				}
+				last = Some(quiz_count);
+				let quiz = Self::get_quiz_by_index(quiz_count)
+					.and_then(|quiz_id| Self::get_quiz(&quiz_id).map(|quiz| (quiz_id, quiz)));
+				if let Some((quiz_id, quiz)) = quiz {
+					page.push((quiz_count, Self::summarize(&quiz_id, quiz)));
+				}
+			}
+			let next = last.filter(|last| *last < latest);
+			(page, next)
+		}
+
+		// the money related terms of a quiz, `None` if it does not exist
+		pub fn quiz_terms(
+			quiz_count: u64,
+		) -> Option<QuizTerms<T>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			let quiz = Self::get_quiz(&quiz_id)?;
+			let attempt_fee = T::AttemptFee::get();
+			Some(QuizTerms::<T> {
+				cost_per_wrong_answer: Self::whole_tokens(T::TokensPerQuestion::get()),
+				stretch_multiplier: T::StretchMultiplier::get(),
+				attempt_fee,
+				stake: quiz.stake,
+				pass_threshold: quiz.pass_threshold,
+				max_charge: Self::max_charge(&quiz, false),
+				prize_pool: Self::get_prize_pool(&quiz_id),
+			})
+		}
+
+		// the weight a contracts chain extension charges with `env.charge_weight` before it forwards
+		// `func_id` to `extension_call`, `None` for ids that are not known
+		pub fn extension_weight(
+			func_id: u32,
+		) -> Option<Weight> {
+			match func_id {
+				EXTENSION_GET_QUIZ => Some(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads(2))),
+				EXTENSION_MAX_SCORE => Some(BASE_WEIGHT.saturating_add(T::DbWeight::get().reads(2))),
+				EXTENSION_ATTEMPT => Some(Self::attempt_quiz_weight()),
+				_ => None,
+			}
+		}
+
+		// the logic behind the contracts chain extension, `caller` being the calling contract. The
+		// whole input has to decode, the output is the SCALE encoded result. This runtime does not
+		// include the contracts pallet, a runtime that does implements its `ChainExtension` by
+		// charging `extension_weight` and forwarding here with the address of the contract
+		pub fn extension_call(
+			func_id: u32,
+			caller: T::AccountId,
+			input: &[u8],
+		) -> Result<Vec<u8>, DispatchError> {
+			fn decode_all<V: Decode, T: Config>(input: &[u8]) -> Result<V, Error<T>> {
+				let mut input = input;
+				let value = V::decode(&mut input).map_err(|_| <Error<T>>::DecodeFailed)?;
+				ensure!(input.is_empty(), <Error<T>>::DecodeFailed);
+				Ok(value)
+			}
+
+			match func_id {
+				EXTENSION_GET_QUIZ => {
+					let quiz_count: u64 = decode_all::<_, T>(input)?;
+					let quiz_id = T::Hashing::hash_of(&quiz_count);
+					let summary = Self::get_quiz(&quiz_id).map(|quiz| Self::summarize(&quiz_id, quiz));
+					Ok(summary.encode())
+				},
+				EXTENSION_MAX_SCORE => {
+					let quiz_count: u64 = decode_all::<_, T>(input)?;
+					let quiz_id = T::Hashing::hash_of(&quiz_count);
+					let max_score = Self::get_quiz(&quiz_id).map(|quiz| Self::questions_per_attempt(&quiz_id, &quiz) as u8);
+					Ok(max_score.encode())
+				},
+				EXTENSION_ATTEMPT => {
+					ensure!(T::AllowContractAttempts::get(), <Error<T>>::ContractAttemptsDisabled);
+					let (quiz_count, submission): (u64, Solution) = decode_all::<_, T>(input)?;
+					// the contract pays for and is rated on its attempt like any player
+					let receipt = Self::submit_attempt(caller, quiz_count, submission, None)
+						.map_err(|error| error.error)?;
+					Ok(receipt.encode())
+				},
+				_ => Err(<Error<T>>::UnknownExtensionFunction.into()),
+			}
+		}
+
+		// the overview of a stored quiz
+		pub fn summarize(
+			quiz_id: &T::Hash,
+			quiz: Quiz<T>,
+		) -> QuizSummary<T> {
+			QuizSummary::<T> {
+				owner: quiz.owner,
+				rating: quiz.rating,
+				lang: quiz.lang,
+				version: quiz.version,
+				attempts: Self::get_attempt_count(quiz_id),
+				expires_at: quiz.expires_at,
+			}
+		}
+
+		// the answer explanations of a quiz, only handed out to players who have attempted it. This
+		// keeps them out of the quiz APIs, raw storage remains readable by anyone
+		pub fn explanations_for(
+			quiz_count: u64,
+			who: &T::AccountId,
+		) -> Option<Vec<ExplanationOf<T>>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			if !<AttemptResults<T>>::contains_key(&quiz_id, who) {
+				return None;
+			}
+			Self::get_explanations(&quiz_id)
+		}
+
+		// the number of submitted attempts of a quiz
+		pub fn attempt_count(
+			quiz_count: u64,
+		) -> u32 {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			Self::get_attempt_count(&quiz_id)
+		}
+
+		// the answer key of a quiz, only available once the quiz has expired
+		pub fn reveal_solution(
+			quiz_count: u64,
+		) -> Result<Solution, Error<T>> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			ensure!(!<Quizzes<T>>::contains_key(&quiz_id), <Error<T>>::QuizStillActive);
+			Self::get_revealed_solution(&quiz_id).ok_or(<Error<T>>::QuizDoesNotExist)
+		}
+
+		// the index of the `T::BlocksPerDay` long window the block falls in
+		pub fn daily_window(
+			block_number: T::BlockNumber,
+		) -> T::BlockNumber {
+			let blocks_per_day = T::BlocksPerDay::get();
+			if blocks_per_day.is_zero() {
+				return Zero::zero();
+			}
+			block_number / blocks_per_day
+		}
+
+		// whether `who` may submit another attempt in the current window, a new window starts the
+		// count over
+		pub fn within_daily_quota(
+			who: &T::AccountId,
+		) -> bool {
+			let quota = T::DailyAttemptQuota::get();
+			if quota == 0 {
+				return true;
+			}
+			let (window, count) = Self::get_daily_attempts(who);
+			window != Self::daily_window(<frame_system::Pallet<T>>::block_number()) || count < quota
+		}
+
+		// whether `who` owns the quiz, `false` if it does not exist
+		pub fn is_owner(
+			quiz_count: u64,
+			who: &T::AccountId,
+		) -> bool {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			Self::get_quiz(&quiz_id).map_or(false, |quiz| &quiz.owner == who)
+		}
+
+		// the block in which `check_and_delete_quiz` will remove the quiz
+		pub fn deletion_block(
+			quiz_count: u64,
+		) -> Option<T::BlockNumber> {
+			let quiz_id = T::Hashing::hash_of(&quiz_count);
+			Self::get_quiz(&quiz_id).map(|quiz| quiz.expires_at)
+		}
+
+		// the quizzes `check_and_delete_quiz` will remove in the given block, bucket by bucket
+		pub fn scheduled_deletions(
+			block_number: T::BlockNumber,
+		) -> Vec<T::Hash> {
+			let block_hash = T::Hashing::hash_of(&block_number.saturated_into::<u64>());
+			(0..DELETION_BUCKETS)
+				.flat_map(|bucket| Self::get_quiz_to_delete(&block_hash, bucket).into_inner())
+				.collect()
+		}
+
+		// an amount configured in `T::TokenUnit`s in the smallest units balances are kept in
+		pub fn whole_tokens(
+			amount: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			amount.saturating_mul(T::TokenUnit::get())
+		}
+
+		// the cost of an attempt with `correct` right answers on a quiz of the given rating, every
+		// wrong answer costs `T::TokensPerQuestion` token units times `1 + rating * T::RatingCostFactor`; all
+		// token costs are computed here
+		pub fn attempt_cost(
+			correct: u8,
+			rating: u8,
+		) -> BalanceOf<T> {
+			// computed in the balance type, a large per question cost would overflow `u32`
+			let wrong : BalanceOf<T> = (QUESTIONS_PER_QUIZ as u32).saturating_sub(correct as u32).into();
+			let base = wrong.saturating_mul(Self::whole_tokens(T::TokensPerQuestion::get()));
+			let premium = Self::scale_cost(base, T::RatingCostFactor::get()).saturating_mul(rating.into());
+			base.saturating_add(premium)
+		}
+
+		// the per wrong answer charge of an attempt, including the premium of a stretch attempt
+		pub fn charge_for(
+			correct: u8,
+			is_stretch: bool,
+			rating: u8,
+		) -> BalanceOf<T> {
+			let token_to_pay = Self::attempt_cost(correct, rating);
+			if is_stretch {
+				token_to_pay.saturating_mul(T::StretchMultiplier::get().into())
+			} else {
+				token_to_pay
+			}
+		}
+
+		// what the player has to be able to pay for an attempt, the stake on staked quizzes and the
+		// charge otherwise
+		pub fn at_risk(
+			quiz: &Quiz<T>,
+			charge: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			if quiz.stake.is_zero() { charge } else { quiz.stake }
+		}
+
+		// the share of `charge` a referrer gets, zero when it would not lift a new account to the
+		// existential deposit and the transfer to it would fail
+		pub fn referral_cut(
+			referrer: &T::AccountId,
+			charge: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let cut = Self::scale_cost(charge, T::ReferralCut::get());
+			if T::Currency::total_balance(referrer).saturating_add(cut) < T::Currency::minimum_balance() {
+				Zero::zero()
+			} else {
+				cut
+			}
+		}
+
+		// whether `who` still holds the existential deposit after paying `amount`, payments are made
+		// with `KeepAlive` and would otherwise fail as an opaque `InsufficientBalance`
+		pub fn keeps_alive(
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> bool {
+			T::Currency::free_balance(who).saturating_sub(amount) >= T::Currency::minimum_balance()
+		}
+
+		// scales a token cost by a fraction, rounding as configured by `T::TokenCostRounding`
+		pub fn scale_cost(
+			amount: BalanceOf<T>,
+			fraction: Perbill,
+		) -> BalanceOf<T> {
+			match T::TokenCostRounding::get() {
+				CostRounding::Down => fraction.mul_floor(amount),
+				CostRounding::Up => fraction.mul_ceil(amount),
+				CostRounding::Nearest => fraction * amount,
+			}
+		}
+
+		// whether attempting the quiz can cost the player any tokens
+		pub fn is_monetized(
+			quiz: &Quiz<T>,
+		) -> bool {
+			!Self::max_charge(quiz, false).is_zero()
+		}
+
+		// the most a single attempt of the quiz can cost the player, the stretch premium and the
+		// attempt fee included
+		pub fn max_charge(
+			quiz: &Quiz<T>,
+			is_stretch: bool,
+		) -> BalanceOf<T> {
+			Self::at_risk(quiz, Self::charge_for(0, is_stretch, quiz.rating)).saturating_add(T::AttemptFee::get())
+		}
+
+		// escrows the player's stake and refunds it when the score in hundredths of a point reaches
+		// the pass threshold, otherwise it is forfeited to the owner; returns whether the player passed
+		pub fn settle_stake(
+			player: &T::AccountId,
+			quiz: &Quiz<T>,
+			score: u32,
+		) -> Result<bool, Error<T>> {
+			T::Currency::reserve(player, quiz.stake).map_err(|_| <Error<T>>::InsufficientBalance)?;
+			let passed = score >= rating::from_points(quiz.pass_threshold);
+			if passed {
+				T::Currency::unreserve(player, quiz.stake);
+			} else {
+				// the forfeited stake stays reserved on the owner until the lock period elapses
+				T::Currency::repatriate_reserved(player, &quiz.owner, quiz.stake, BalanceStatus::Reserved)
+					.map_err(|_| <Error<T>>::InsufficientBalance)?;
+				Self::lock_earnings(&quiz.owner, quiz.stake);
+			}
+			Ok(passed)
+		}
+
+		// records already reserved earnings of an owner as claimable once `T::EarningsLockPeriod`
+		// blocks have passed, rounded up to the next whole lock period
+		pub fn lock_earnings(
+			owner: &T::AccountId,
+			amount: BalanceOf<T>,
+		) {
+			if amount.is_zero() {
+				return;
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			let unlock_at = Self::lock_boundary(now.saturating_add(T::EarningsLockPeriod::get()));
+			<EarningsLocks<T>>::mutate(owner, |locks| Self::add_earnings_lock(locks, unlock_at, amount, now));
+			Self::deposit_event(Event::EarningsLocked(owner.clone(), amount, unlock_at));
+		}
+
+		// the first multiple of `T::EarningsLockPeriod` at or after `block`. Locks sharing it share
+		// one entry, so an owner has at most two locks that are still pending
+		pub fn lock_boundary(
+			block: T::BlockNumber,
+		) -> T::BlockNumber {
+			let period = T::EarningsLockPeriod::get();
+			if period.is_zero() {
+				return block;
+			}
+			let rest = block % period;
+			if rest.is_zero() {
+				block
+			} else {
+				block.saturating_add(period - rest)
+			}
+		}
+
+		// adds `amount` unlocking at `unlock_at` to the locks of an owner. Earnings already unlocked
+		// at `now` are merged into one entry, so together with the at most two pending locks the list
+		// never holds more than three entries however long the owner waits to claim
+		pub fn add_earnings_lock(
+			locks: &mut Vec<(T::BlockNumber, BalanceOf<T>)>,
+			unlock_at: T::BlockNumber,
+			amount: BalanceOf<T>,
+			now: T::BlockNumber,
+		){
+			let unlocked = locks.iter()
+				.filter(|(at, _)| *at <= now)
+				.fold(BalanceOf::<T>::zero(), |unlocked, (_, locked)| unlocked.saturating_add(*locked));
+			locks.retain(|(at, _)| *at > now);
+			if !unlocked.is_zero() {
+				locks.push((now, unlocked));
+			}
+			let unlock_at = if unlock_at <= now { now } else { Self::lock_boundary(unlock_at) };
+			match locks.iter_mut().find(|(at, _)| *at == unlock_at) {
+				Some((_, locked)) => *locked = locked.saturating_add(amount),
+				None => locks.push((unlock_at, amount)),
+			}
+		}
+
+		// withdraws a flat fee, e.g. the attempt fee, and hands it to `T::AttemptFeeDestination`
+		pub fn charge_attempt_fee(
+			player: &T::AccountId,
+			fee: BalanceOf<T>,
+		) -> Result<(), Error<T>> {
+			if fee.is_zero() {
+				return Ok(());
+			}
+			let imbalance = T::Currency::withdraw(player, fee, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive)
+				.map_err(|_| <Error<T>>::InsufficientBalance)?;
+			T::AttemptFeeDestination::on_unbalanced(imbalance);
 			Ok(())
 		}
 
+		// pays part of an attempt's charge, every non zero transfer is reported for indexers
 		pub fn transfer_tokens_to_owner(
+			quiz_count: u64,
 			sender: &T::AccountId,
 			receiver: &T::AccountId,
 			amount: BalanceOf<T>,
@@ -307,8 +3785,13 @@ pub mod pallet {
 			let free_balance = T::Currency::free_balance(&sender);
 			if free_balance < amount {
 				Err(<Error<T>>::InsufficientBalance)
+			}else if !Self::keeps_alive(sender, amount) {
+				Err(<Error<T>>::WouldReapAccount)
 			}else{
 				T::Currency::transfer(&sender, &receiver, amount, ExistenceRequirement::KeepAlive).map_err(|_|<Error<T>>::InsufficientBalance)?;
+				if !amount.is_zero() {
+					Self::deposit_event(Event::TokensCharged(quiz_count, sender.clone(), receiver.clone(), amount));
+				}
 				Ok(())
 			}
 		}
@@ -0,0 +1,353 @@
+use crate as pallet_template;
+use crate::{CostRounding, OnPerfectScore, OnQuizCompleted, OnQuizProposed, Question};
+use frame_support::{
+	parameter_types,
+	traits::{Currency, GenesisBuild, OnInitialize, SortedMembers, VestingSchedule},
+	weights::Weight,
+	PalletId,
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchResult, Perbill,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		QuizModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const DAVE: u64 = 4;
+pub const EVE: u64 = 5;
+// an approved creator without funds, tests give it the balance they need
+pub const POOR: u64 = 8;
+// an endowed account that is not an approved creator
+pub const OUTSIDER: u64 = 99;
+// an account that does not exist until something is paid to it
+pub const NEWCOMER: u64 = 77;
+
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+// parameters a test can change with `set`, kept per thread so every test starts from the defaults
+macro_rules! dynamic_parameters {
+	($($name:ident: $type:ty = $default:expr;)*) => {
+		$(
+			pub struct $name;
+
+			impl $name {
+				fn value() -> &'static std::thread::LocalKey<RefCell<$type>> {
+					thread_local! {
+						static VALUE: RefCell<$type> = RefCell::new($default);
+					}
+					&VALUE
+				}
+
+				// not every parameter is changed by a test
+				#[allow(dead_code)]
+				pub fn set(value: $type) {
+					Self::value().with(|current| *current.borrow_mut() = value);
+				}
+			}
+
+			impl frame_support::traits::Get<$type> for $name {
+				fn get() -> $type {
+					Self::value().with(|current| current.borrow().clone())
+				}
+			}
+		)*
+	};
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+dynamic_parameters! {
+	ExistentialDeposit: u64 = 1;
+	TokenUnit: u64 = 10;
+	RatingCostFactor: Perbill = Perbill::zero();
+	TokenCostRounding: CostRounding = CostRounding::Down;
+	MultiSelectPartialCredit: Perbill = Perbill::zero();
+	WrongAnswerPenalty: Perbill = Perbill::zero();
+	AttemptFee: u64 = 0;
+	ShuffleOptions: bool = false;
+	MaxActiveQuizzes: u32 = 100;
+	AttemptCooldown: u64 = 0;
+	ResetCooldownOnImprovement: bool = false;
+	MinAttemptsForRating: u32 = 0;
+	RatingAlpha: Perbill = Perbill::from_percent(50);
+	RejectUniformSolutions: bool = false;
+	MaxDeletionBucketLen: u32 = 64;
+	ExpiryWeightBudget: Weight = 1_000_000_000_000;
+	ExpiryGracePeriod: u64 = 0;
+	MaxConcurrentAttempts: u32 = 0;
+	AllowContractAttempts: bool = false;
+	ArchiveExpiredQuizzes: bool = false;
+	SpeedBonus: Perbill = Perbill::zero();
+	DailyAttemptQuota: u32 = 0;
+}
+
+parameter_types! {
+	pub const TokensPerQuestion: u64 = 1;
+	pub const QuizDeposit: u64 = 100;
+	pub const QuizDepositPerByte: u64 = 1;
+	pub const SlashReportThreshold: u32 = 2;
+	pub const MaxTextLen: u32 = 64;
+	pub const MinTextLen: u32 = 2;
+	pub const MaxRating: u8 = 5;
+	pub const StretchMargin: u8 = 1;
+	pub const StretchMultiplier: u32 = 2;
+	pub OrdinalPartialCredit: Perbill = Perbill::from_percent(50);
+	pub const MaxBulkDelete: u32 = 3;
+	pub const MaxQuizBatch: u32 = 3;
+	pub const MaxRefundsPerCall: u32 = 2;
+	pub const EarningsLockPeriod: u64 = 10;
+	pub ReferralCut: Perbill = Perbill::from_percent(10);
+	pub const QuizPalletId: PalletId = PalletId(*b"py/quizm");
+	pub const TournamentWinners: u32 = 2;
+	pub const RatingMilestoneStep: u8 = 1;
+	pub const MaxWrongAttempts: u32 = 3;
+	pub const StrikeScoreThreshold: u8 = 2;
+	pub const SpeedBonusWindow: u64 = 10;
+	pub const RatingResetFee: u64 = 50;
+	pub const RatingResetCooldown: u64 = 100;
+	pub const BlocksPerDay: u64 = 100;
+	pub const VestingThreshold: u64 = 1_000;
+	pub const VestingPeriod: u64 = 100;
+}
+
+// the accounts allowed to create quizzes, everyone but `OUTSIDER`
+pub struct Creators;
+
+impl SortedMembers<u64> for Creators {
+	fn sorted_members() -> Vec<u64> {
+		(1..=9).collect()
+	}
+}
+
+thread_local! {
+	static COMPLETED_ATTEMPTS: RefCell<Vec<(u64, u64, u8)>> = RefCell::new(Vec::new());
+	static PERFECT_SCORES: RefCell<Vec<(u64, u64)>> = RefCell::new(Vec::new());
+	static VESTING_SCHEDULES: RefCell<Vec<(u64, u64, u64, u64)>> = RefCell::new(Vec::new());
+}
+
+// every `(who, quiz_count, score)` reported to `OnQuizCompleted`
+pub fn completed_attempts() -> Vec<(u64, u64, u8)> {
+	COMPLETED_ATTEMPTS.with(|completed| completed.borrow().clone())
+}
+
+// every `(who, quiz_count)` reported to `OnPerfectScore`
+pub fn perfect_scores() -> Vec<(u64, u64)> {
+	PERFECT_SCORES.with(|perfect| perfect.borrow().clone())
+}
+
+// every `(who, locked, per_block, starting_block)` added to `MockVesting`
+pub fn vesting_schedules() -> Vec<(u64, u64, u64, u64)> {
+	VESTING_SCHEDULES.with(|schedules| schedules.borrow().clone())
+}
+
+// records the hooks the pallet calls, approves every quiz that does not mention spam
+pub struct MockHooks;
+
+impl OnQuizCompleted<u64> for MockHooks {
+	fn on_completed(who: &u64, quiz_count: u64, score: u8) {
+		COMPLETED_ATTEMPTS.with(|completed| completed.borrow_mut().push((*who, quiz_count, score)));
+	}
+}
+
+impl OnPerfectScore<u64> for MockHooks {
+	fn on_perfect_score(who: &u64, quiz_count: u64) {
+		PERFECT_SCORES.with(|perfect| perfect.borrow_mut().push((*who, quiz_count)));
+	}
+}
+
+impl OnQuizProposed<u64> for MockHooks {
+	fn approve(_owner: &u64, questions: &[Question]) -> bool {
+		questions.iter().all(|question| !question.statement.windows(4).any(|word| word == b"spam"))
+	}
+}
+
+// records the vesting schedules of large prizes instead of locking anything
+pub struct MockVesting;
+
+impl VestingSchedule<u64> for MockVesting {
+	type Moment = u64;
+	type Currency = Balances;
+
+	fn vesting_balance(who: &u64) -> Option<u64> {
+		let locked: u64 = vesting_schedules().iter().filter(|(vested, ..)| vested == who).map(|(_, locked, ..)| locked).sum();
+		if locked == 0 { None } else { Some(locked) }
+	}
+
+	fn add_vesting_schedule(who: &u64, locked: u64, per_block: u64, starting_block: u64) -> DispatchResult {
+		VESTING_SCHEDULES.with(|schedules| schedules.borrow_mut().push((*who, locked, per_block, starting_block)));
+		Ok(())
+	}
+
+	fn can_add_vesting_schedule(_who: &u64, _locked: u64, _per_block: u64, _starting_block: u64) -> DispatchResult {
+		Ok(())
+	}
+
+	fn remove_vesting_schedule(_who: &u64, _schedule_index: u32) -> DispatchResult {
+		Ok(())
+	}
+}
+
+impl pallet_template::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type TokensPerQuestion = TokensPerQuestion;
+	type TokenUnit = TokenUnit;
+	type QuizDeposit = QuizDeposit;
+	type QuizDepositPerByte = QuizDepositPerByte;
+	type SlashReportThreshold = SlashReportThreshold;
+	type Slashed = ();
+	type MaxTextLen = MaxTextLen;
+	type MinTextLen = MinTextLen;
+	type MaxRating = MaxRating;
+	type StretchMargin = StretchMargin;
+	type StretchMultiplier = StretchMultiplier;
+	type RatingCostFactor = RatingCostFactor;
+	type TokenCostRounding = TokenCostRounding;
+	type OrdinalPartialCredit = OrdinalPartialCredit;
+	type MultiSelectPartialCredit = MultiSelectPartialCredit;
+	type WrongAnswerPenalty = WrongAnswerPenalty;
+	type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+	type CreateOrigin = frame_system::EnsureSignedBy<Creators, u64>;
+	type AttemptFee = AttemptFee;
+	type AttemptFeeDestination = ();
+	type ShuffleOptions = ShuffleOptions;
+	type MaxActiveQuizzes = MaxActiveQuizzes;
+	type MaxBulkDelete = MaxBulkDelete;
+	type MaxQuizBatch = MaxQuizBatch;
+	type MaxRefundsPerCall = MaxRefundsPerCall;
+	type EarningsLockPeriod = EarningsLockPeriod;
+	type ReferralCut = ReferralCut;
+	type PalletId = QuizPalletId;
+	type TournamentWinners = TournamentWinners;
+	type AttemptCooldown = AttemptCooldown;
+	type ResetCooldownOnImprovement = ResetCooldownOnImprovement;
+	type MinAttemptsForRating = MinAttemptsForRating;
+	type RatingMilestoneStep = RatingMilestoneStep;
+	type RatingAlpha = RatingAlpha;
+	type MaxWrongAttempts = MaxWrongAttempts;
+	type StrikeScoreThreshold = StrikeScoreThreshold;
+	type RejectUniformSolutions = RejectUniformSolutions;
+	type MaxDeletionBucketLen = MaxDeletionBucketLen;
+	type ExpiryWeightBudget = ExpiryWeightBudget;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type MaxConcurrentAttempts = MaxConcurrentAttempts;
+	type AllowContractAttempts = AllowContractAttempts;
+	type ArchiveExpiredQuizzes = ArchiveExpiredQuizzes;
+	type SpeedBonus = SpeedBonus;
+	type SpeedBonusWindow = SpeedBonusWindow;
+	type RatingResetFee = RatingResetFee;
+	type RatingResetCooldown = RatingResetCooldown;
+	type DailyAttemptQuota = DailyAttemptQuota;
+	type BlocksPerDay = BlocksPerDay;
+	type VestingThreshold = VestingThreshold;
+	type VestingPeriod = VestingPeriod;
+	type OnQuizCompleted = MockHooks;
+	type OnPerfectScore = MockHooks;
+	type OnQuizProposed = MockHooks;
+	type Vesting = MockVesting;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: [ALICE, BOB, CHARLIE, DAVE, EVE, OUTSIDER].iter().map(|who| (*who, INITIAL_BALANCE)).collect(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// moves to block `n`, running the quiz expiry of every block on the way
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		QuizModule::on_initialize(next);
+	}
+}
+
+// the events of the quiz pallet deposited so far, oldest first
+pub fn quiz_events() -> Vec<pallet_template::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::QuizModule(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+// sets the free balance of `who`, for tests that need a poorer or richer account
+pub fn fund(who: u64, amount: u64) {
+	Balances::make_free_balance_be(&who, amount);
+}
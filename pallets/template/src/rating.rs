@@ -0,0 +1,138 @@
+// Rating and score arithmetic. Ratings and scores are kept in hundredths of a point so partial
+// credit and small steps of the moving average add up instead of rounding away, and are shown and
+// compared as `u8` points. Every helper here saturates or widens before it adds or subtracts, so
+// nothing wraps around.
+
+use frame_support::sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+// the stored units a rating or score point is made of
+pub(crate) const RATING_SCALE: u32 = 100;
+
+// a rating of whole points in stored units
+pub(crate) fn from_points(
+	points: u8,
+) -> u32 {
+	points as u32 * RATING_SCALE
+}
+
+// a stored rating or score rounded to the nearest point, halves rounding up, capped at 255
+pub(crate) fn to_points(
+	rating: u32,
+) -> u8 {
+	(rating.saturating_add(RATING_SCALE / 2) / RATING_SCALE).min(u8::MAX as u32) as u8
+}
+
+// moves the stored `rating` the fraction `alpha` of the way towards the stored `score`. `score`
+// is capped at `max` points first. The result always lies between `rating` and the capped score,
+// both included, so it never exceeds the larger of the two
+pub(crate) fn rating_average(
+	rating: u32,
+	score: u32,
+	alpha: Perbill,
+	max: u8,
+) -> u32 {
+	let score = score.min(from_points(max));
+	if score >= rating {
+		rating.saturating_add(alpha * (score - rating))
+	} else {
+		rating.saturating_sub(alpha * (rating - score))
+	}
+}
+
+// the lowest rating a player needs to attempt a quiz of `quiz_rating` without it being a stretch,
+// one point below the quiz's rating and 0 for quizzes rated 0 or 1
+pub(crate) fn min_required(
+	quiz_rating: u8,
+) -> u8 {
+	quiz_rating.saturating_sub(1)
+}
+
+// whether `rating` is at most `margin` points below `required`. The margin saturates at 255, so a
+// large margin admits every rating instead of wrapping around to a small one
+pub(crate) fn within_margin(
+	rating: u8,
+	required: u8,
+	margin: u8,
+) -> bool {
+	rating.saturating_add(margin) >= required
+}
+
+// every multiple of `step` above `old` up to and including `new`, empty if `step` is 0 or the
+// rating did not rise. Computed in `u16` so the point after 255 does not wrap to 0
+pub(crate) fn milestones_between(
+	old: u8,
+	new: u8,
+	step: u8,
+) -> Vec<u8> {
+	if step == 0 {
+		return Vec::new();
+	}
+	let step = step as u16;
+	(old as u16 / step + 1..=new as u16 / step)
+		.map(|milestone| (milestone * step) as u8)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn points_convert_at_the_bounds() {
+		assert_eq!(from_points(0), 0);
+		assert_eq!(from_points(u8::MAX), 25_500);
+		assert_eq!(to_points(0), 0);
+		assert_eq!(to_points(49), 0);
+		assert_eq!(to_points(50), 1);
+		assert_eq!(to_points(25_500), u8::MAX);
+		// stored values past 255 points are capped, not wrapped
+		assert_eq!(to_points(u32::MAX), u8::MAX);
+	}
+
+	#[test]
+	fn required_rating_does_not_underflow() {
+		assert_eq!(min_required(0), 0);
+		assert_eq!(min_required(1), 0);
+		assert_eq!(min_required(u8::MAX), 254);
+	}
+
+	#[test]
+	fn average_stays_between_rating_and_score() {
+		let half = Perbill::from_percent(50);
+		assert_eq!(rating_average(0, 0, half, 0), 0);
+		assert_eq!(rating_average(0, from_points(u8::MAX), half, u8::MAX), 12_750);
+		assert_eq!(rating_average(from_points(u8::MAX), 0, half, u8::MAX), 12_750);
+		assert_eq!(rating_average(from_points(u8::MAX), from_points(u8::MAX), half, u8::MAX), 25_500);
+		// the score is capped at the highest rating first
+		assert_eq!(rating_average(0, from_points(u8::MAX), Perbill::one(), 5), 500);
+	}
+
+	#[test]
+	fn average_saturates_at_the_extremes() {
+		assert_eq!(rating_average(u32::MAX, 0, Perbill::one(), 0), 0);
+		assert_eq!(rating_average(u32::MAX, 0, Perbill::zero(), u8::MAX), u32::MAX);
+		assert_eq!(rating_average(0, u32::MAX, Perbill::one(), u8::MAX), 25_500);
+	}
+
+	#[test]
+	fn margin_does_not_wrap() {
+		assert!(within_margin(0, u8::MAX, u8::MAX));
+		assert!(!within_margin(0, u8::MAX, 254));
+		assert!(within_margin(u8::MAX, u8::MAX, 0));
+		assert!(within_margin(u8::MAX, 0, u8::MAX));
+		assert!(within_margin(0, 0, 0));
+	}
+
+	#[test]
+	fn milestones_up_to_255_are_reported_once() {
+		assert_eq!(milestones_between(250, u8::MAX, 5), vec![u8::MAX]);
+		assert_eq!(milestones_between(0, u8::MAX, u8::MAX), vec![u8::MAX]);
+		assert_eq!(milestones_between(0, u8::MAX, 128), vec![128]);
+		assert_eq!(milestones_between(0, 3, 1), vec![1, 2, 3]);
+		assert!(milestones_between(u8::MAX, u8::MAX, 1).is_empty());
+		// a falling rating or a step of 0 reports nothing
+		assert!(milestones_between(5, 0, 1).is_empty());
+		assert!(milestones_between(0, 3, 0).is_empty());
+	}
+}
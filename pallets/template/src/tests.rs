@@ -0,0 +1,1986 @@
+use crate::{
+	mock::*,
+	pallet::{QuizCnt, QuizToDelete, Quizzes, Solutions, UserAttempts},
+	rating, Answer, AttemptReceipt, AttemptStatus, CostRounding, Error, Event as QuizEvent,
+	LanguageCode, Question, Quiz, QuizPayload, Solution, DELETION_BUCKETS, DELETION_SPILL_BLOCKS,
+	EXTENSION_ATTEMPT, EXTENSION_GET_QUIZ, EXTENSION_MAX_SCORE, MAX_STARS, OPTIONS_PER_QUESTION,
+	QUESTIONS_PER_QUIZ, STORAGE_VERSION,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{have_storage_value, put_storage_value},
+	traits::{Get, Hooks, StorageVersion},
+	weights::GetDispatchInfo,
+	BoundedVec, StorageHasher, Twox64Concat,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, DispatchResult, Perbill,
+};
+
+const LANG: LanguageCode = *b"en";
+
+// what one wrong answer costs with the mock's defaults
+const WRONG_ANSWER: u64 = 10;
+
+fn question(statement: &[u8]) -> Question {
+	Question {
+		statement: statement.to_vec(),
+		option1: b"one".to_vec(),
+		option2: b"two".to_vec(),
+		option3: b"three".to_vec(),
+		option4: b"four".to_vec(),
+		lang: None,
+		ordinal: false,
+		multi_select: false,
+	}
+}
+
+fn questions() -> Vec<Question> {
+	vec![question(b"first"), question(b"second"), question(b"third"), question(b"fourth"), question(b"fifth")]
+}
+
+// the answer key of `questions`
+fn key() -> Solution {
+	Solution::from_answers([Answer::Option1, Answer::Option2, Answer::Option3, Answer::Option4, Answer::Option1])
+}
+
+// a submission answering the first `correct` questions right and the others with an option that
+// is not next to the right one
+fn submission(correct: usize) -> Solution {
+	let mut answers = key().answers();
+	for answer in answers.iter_mut().skip(correct) {
+		*answer = Answer::new((answer.index().unwrap() + 2) % OPTIONS_PER_QUESTION).unwrap();
+	}
+	Solution::from_answers(answers)
+}
+
+fn with_answer(solution: Solution, position: usize, answer: Answer) -> Solution {
+	let mut answers = solution.answers();
+	answers[position] = answer;
+	Solution::from_answers(answers)
+}
+
+fn payload() -> QuizPayload {
+	QuizPayload { questions: questions(), lang: LANG, solution: key(), rating: 0 }
+}
+
+fn create(owner: u64, questions: Vec<Question>, solution: Solution, rating: u8) -> DispatchResult {
+	QuizModule::add_quiz(
+		Origin::signed(owner),
+		questions[0].clone(),
+		questions[1].clone(),
+		questions[2].clone(),
+		questions[3].clone(),
+		questions[4].clone(),
+		solution,
+		rating,
+		LANG,
+	)
+}
+
+// creates a quiz of `questions` keyed by `key`, returning its count
+fn add_quiz(owner: u64, rating: u8) -> u64 {
+	assert_ok!(create(owner, questions(), key(), rating));
+	QuizModule::get_latest_quiz()
+}
+
+fn quiz_id(quiz_count: u64) -> H256 {
+	BlakeTwo256::hash_of(&quiz_count)
+}
+
+// an attempt without referrer, with the error stripped of its weight so `assert_noop` can match it
+fn attempt(who: u64, quiz_count: u64, submission: Solution) -> DispatchResult {
+	attempt_referred(who, quiz_count, submission, None)
+}
+
+fn attempt_referred(who: u64, quiz_count: u64, submission: Solution, referrer: Option<u64>) -> DispatchResult {
+	QuizModule::attempt_quiz(Origin::signed(who), quiz_count, submission, referrer)
+		.map(|_| ())
+		.map_err(|error| error.error)
+}
+
+fn score(who: u64, quiz_count: u64) -> u8 {
+	QuizModule::get_attempt_result(&quiz_id(quiz_count), &who).unwrap().score
+}
+
+fn last_quiz_event() -> QuizEvent<Test> {
+	quiz_events().pop().unwrap()
+}
+
+fn has_event(event: QuizEvent<Test>) -> bool {
+	quiz_events().contains(&event)
+}
+
+// the submission picking the options of `solution` at the positions `order` displays them at
+fn displayed(order: &[[u8; OPTIONS_PER_QUESTION as usize]], solution: Solution) -> Solution {
+	let mut answers = solution.answers();
+	for (answer, order) in answers.iter_mut().zip(order) {
+		if let Some(option) = answer.index() {
+			let position = order.iter().position(|shown| *shown == option).unwrap() as u8;
+			*answer = Answer::new(position).unwrap();
+		}
+	}
+	Solution::from_answers(answers)
+}
+
+// `size` questions for a question pool with their answers
+fn pool(size: u8) -> (Vec<Question>, Vec<Answer>) {
+	let questions = (0..size).map(|index| question(&[&b"pooled "[..], &[b'a' + index]].concat())).collect();
+	let answers = (0..size).map(|index| Answer::new(index % OPTIONS_PER_QUESTION).unwrap()).collect();
+	(questions, answers)
+}
+
+#[test]
+fn cancelling_a_started_attempt_frees_it_for_a_fresh_one() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		// the worst case of every answer wrong is reserved
+		assert_eq!(Balances::reserved_balance(BOB), 5 * WRONG_ANSWER);
+		assert_noop!(QuizModule::start_attempt(Origin::signed(BOB), quiz), Error::<Test>::AttemptAlreadyInProgress);
+
+		assert_ok!(QuizModule::cancel_attempt(Origin::signed(BOB), quiz));
+		assert!(QuizModule::get_attempt_in_progress(&quiz_id(quiz), &BOB).is_none());
+		assert_eq!(QuizModule::get_attempts_in_progress_count(&quiz_id(quiz)), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_noop!(QuizModule::cancel_attempt(Origin::signed(BOB), quiz), Error::<Test>::NoAttemptInProgress);
+
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn starting_is_refused_for_everything_an_attempt_is() {
+	new_test_ext().execute_with(|| {
+		let rated = add_quiz(ALICE, 3);
+		assert_noop!(QuizModule::start_attempt(Origin::signed(BOB), rated), Error::<Test>::UserRatingTooLow);
+
+		let quiz = add_quiz(ALICE, 0);
+		for _ in 0..MaxWrongAttempts::get() {
+			assert_ok!(attempt(BOB, quiz, submission(1)));
+		}
+		assert_noop!(QuizModule::start_attempt(Origin::signed(BOB), quiz), Error::<Test>::AttemptLimitReached);
+
+		let limited = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::set_attempt_limit(Origin::signed(ALICE), limited, 1));
+		assert_ok!(attempt(CHARLIE, limited, key()));
+		assert_noop!(QuizModule::start_attempt(Origin::signed(CHARLIE), limited), Error::<Test>::AttemptLimitReached);
+
+		assert_ok!(QuizModule::clone_quiz(Origin::signed(ALICE), limited));
+		let clone = QuizModule::get_latest_quiz();
+		assert_noop!(QuizModule::start_attempt(Origin::signed(CHARLIE), clone), Error::<Test>::AlreadyAttemptedSource);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(DAVE), clone));
+	});
+}
+
+#[test]
+fn answers_are_built_from_zero_based_indices() {
+	assert_eq!(Answer::new(0), Some(Answer::Option1));
+	assert_eq!(Answer::new(3), Some(Answer::Option4));
+	for index in 0..OPTIONS_PER_QUESTION {
+		assert_eq!(Answer::new(index).and_then(Answer::index), Some(index));
+	}
+	assert_eq!(Answer::Abstain.index(), None);
+}
+
+#[test]
+fn answers_only_represent_existing_options() {
+	assert_eq!(Answer::new(OPTIONS_PER_QUESTION), None);
+	assert_eq!(Answer::new(u8::MAX), None);
+	assert_eq!(Answer::Several(0xff).options(), 0b1111);
+	let submitted = Solution::from_answers([0, 1, 2, 3, 0].map(|index| Answer::new(index).unwrap()));
+	assert_eq!(QuizModule::find_score(submitted, key()), 5);
+}
+
+#[test]
+fn reported_quiz_slashes_the_deposit_at_the_threshold() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = QuizModule::quiz_deposit(&questions());
+		assert_eq!(Balances::reserved_balance(ALICE), deposit);
+		assert_noop!(QuizModule::report_quiz(Origin::signed(ALICE), quiz), Error::<Test>::OwnerCannotReportQuiz);
+		// only players who attempted the quiz may report it
+		assert_noop!(QuizModule::report_quiz(Origin::signed(BOB), quiz), Error::<Test>::ReporterNotVerified);
+
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(QuizModule::report_quiz(Origin::signed(BOB), quiz));
+		assert_noop!(QuizModule::report_quiz(Origin::signed(BOB), quiz), Error::<Test>::AlreadyReported);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert_ok!(QuizModule::report_quiz(Origin::signed(CHARLIE), quiz));
+		assert!(has_event(QuizEvent::CreatorSlashed(quiz, ALICE, deposit)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), quiz));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - deposit);
+	});
+}
+
+#[test]
+fn deposit_below_the_report_threshold_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(QuizModule::report_quiz(Origin::signed(BOB), quiz));
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), quiz));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn deletion_block_is_the_block_the_quiz_is_scheduled_in() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		// quizzes live for 10 blocks
+		let scheduled = System::block_number() + 10;
+		assert_eq!(QuizModule::deletion_block(quiz), Some(scheduled));
+		let bucket = QuizModule::deletion_bucket(&quiz_id(quiz));
+		assert!(QuizModule::get_quiz_to_delete(&BlakeTwo256::hash_of(&scheduled), bucket).contains(&quiz_id(quiz)));
+		assert_eq!(QuizModule::deletion_block(quiz + 1), None);
+	});
+}
+
+#[test]
+fn scheduled_deletions_list_a_new_quiz() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let block = QuizModule::deletion_block(quiz).unwrap();
+		assert_eq!(QuizModule::scheduled_deletions(block), vec![quiz_id(quiz)]);
+		assert!(QuizModule::scheduled_deletions(block + 1).is_empty());
+	});
+}
+
+#[test]
+fn quizzes_expiring_together_are_spread_over_buckets() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..16 {
+			add_quiz(ALICE, 0);
+		}
+		let block = BlakeTwo256::hash_of(&11u64);
+		let used = (0..DELETION_BUCKETS)
+			.filter(|bucket| !QuizModule::get_quiz_to_delete(&block, *bucket).is_empty())
+			.count();
+		assert!(used > 1);
+		assert_eq!(QuizModule::scheduled_deletions(11).len(), 16);
+
+		run_to_block(11);
+		assert_eq!(QuizModule::get_active_quizzes(), 0);
+		assert!(QuizModule::scheduled_deletions(11).is_empty());
+	});
+}
+
+#[test]
+fn full_deletion_buckets_spill_into_the_next_block() {
+	new_test_ext().execute_with(|| {
+		MaxDeletionBucketLen::set(1);
+		let buckets = DELETION_BUCKETS as usize;
+		let quizzes: Vec<u64> = (0..=buckets).map(|_| add_quiz(ALICE, 0)).collect();
+		let block = BlakeTwo256::hash_of(&11u64);
+		for bucket in 0..DELETION_BUCKETS {
+			assert_eq!(QuizModule::get_quiz_to_delete(&block, bucket).len(), 1);
+		}
+		let spilled = quizzes[buckets];
+		assert_eq!(QuizModule::deletion_block(spilled), Some(12));
+		assert_eq!(QuizModule::scheduled_deletions(12), vec![quiz_id(spilled)]);
+
+		// whichever bucket took a quiz, deleting it takes it off the schedule
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), quizzes[1]));
+		assert_eq!(QuizModule::scheduled_deletions(11).len(), buckets - 1);
+
+		run_to_block(12);
+		assert_eq!(QuizModule::get_active_quizzes(), 0);
+	});
+}
+
+#[test]
+fn quizzes_are_refused_once_the_deletion_schedule_is_full() {
+	new_test_ext().execute_with(|| {
+		MaxDeletionBucketLen::set(1);
+		for _ in 0..DELETION_BUCKETS as u32 * DELETION_SPILL_BLOCKS {
+			add_quiz(ALICE, 0);
+		}
+		assert_eq!(QuizModule::deletion_block(QuizModule::get_latest_quiz()), Some(10 + DELETION_SPILL_BLOCKS as u64));
+		assert_noop!(create(ALICE, questions(), key(), 0), Error::<Test>::DeletionScheduleFull);
+	});
+}
+
+#[test]
+fn encoded_quiz_is_stored_as_submitted() {
+	new_test_ext().execute_with(|| {
+		let payload = QuizPayload { rating: 1, ..payload() };
+		assert_ok!(QuizModule::add_quiz_encoded(Origin::signed(ALICE), payload.encode()));
+		let quiz = QuizModule::get_latest_quiz();
+		assert_eq!(QuizModule::quiz_questions(quiz), Some(questions()));
+		assert_eq!(QuizModule::get_solution(&quiz_id(quiz)), Some(key()));
+		assert_eq!(QuizModule::get_quiz(&quiz_id(quiz)).unwrap().rating, 1);
+	});
+}
+
+#[test]
+fn corrupt_encoded_quiz_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let encoded = payload().encode();
+		let truncated = encoded[..encoded.len() - 1].to_vec();
+		assert_noop!(QuizModule::add_quiz_encoded(Origin::signed(ALICE), truncated), Error::<Test>::DecodeFailed);
+		let mut trailing = encoded;
+		trailing.push(0);
+		assert_noop!(QuizModule::add_quiz_encoded(Origin::signed(ALICE), trailing), Error::<Test>::DecodeFailed);
+		let mut short = payload();
+		short.questions.pop();
+		assert_noop!(QuizModule::add_quiz_encoded(Origin::signed(ALICE), short.encode()), Error::<Test>::DecodeFailed);
+	});
+}
+
+#[test]
+fn owner_deletes_all_quizzes_over_several_calls() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..5 {
+			add_quiz(ALICE, 0);
+		}
+		add_quiz(BOB, 0);
+
+		// each call deletes at most `MaxBulkDelete` quizzes
+		assert_ok!(QuizModule::delete_all_my_quizzes(Origin::signed(ALICE), 10));
+		assert_eq!(last_quiz_event(), QuizEvent::OwnerQuizzesDeleted(ALICE, 3, 2));
+		assert_ok!(QuizModule::delete_all_my_quizzes(Origin::signed(ALICE), 10));
+		assert_eq!(last_quiz_event(), QuizEvent::OwnerQuizzesDeleted(ALICE, 2, 0));
+
+		assert_eq!(QuizModule::get_owned_quiz_count(ALICE), 0);
+		assert_eq!(QuizModule::get_active_quizzes(), 1);
+		assert_eq!(QuizModule::get_owned_quiz_count(BOB), 1);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn bulk_deletion_emits_the_count_of_every_quiz() {
+	new_test_ext().execute_with(|| {
+		let first = add_quiz(ALICE, 0);
+		let second = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::delete_all_my_quizzes(Origin::signed(ALICE), 10));
+		assert!(has_event(QuizEvent::QuizDeleted(first, ALICE, 1)));
+		assert!(has_event(QuizEvent::QuizDeleted(second, ALICE, 1)));
+	});
+}
+
+#[test]
+fn bulk_deletion_refunds_the_weight_of_quizzes_not_deleted() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			add_quiz(ALICE, 0);
+		}
+		let declared = crate::Call::<Test>::delete_all_my_quizzes { limit: 10 }.get_dispatch_info().weight;
+		assert_eq!(declared, QuizModule::bulk_delete_weight(MaxBulkDelete::get()));
+
+		let info = QuizModule::delete_all_my_quizzes(Origin::signed(ALICE), 10).unwrap();
+		assert_eq!(info.actual_weight, Some(QuizModule::bulk_delete_weight(3)));
+		let info = QuizModule::delete_all_my_quizzes(Origin::signed(ALICE), 10).unwrap();
+		assert_eq!(info.actual_weight, Some(QuizModule::bulk_delete_weight(1)));
+		assert!(QuizModule::bulk_delete_weight(1) < declared);
+	});
+}
+
+#[test]
+fn attempts_are_counted_per_quiz() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::attempt_count(quiz), 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(attempt(CHARLIE, quiz, submission(3)));
+		assert_eq!(QuizModule::attempt_count(quiz), 2);
+		assert!(has_event(QuizEvent::QuizScore(quiz, CHARLIE, 3, 3, 2)));
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), quiz));
+		assert_eq!(QuizModule::attempt_count(quiz), 0);
+	});
+}
+
+#[test]
+fn wrong_answers_are_penalized_when_configured() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &BOB, submission(3), None), Ok(3));
+
+		WrongAnswerPenalty::set(Perbill::from_percent(50));
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &BOB, submission(3), None), Ok(2));
+		// abstaining is not penalized
+		let abstained = with_answer(with_answer(key(), 3, Answer::Abstain), 4, Answer::Abstain);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &BOB, abstained, None), Ok(3));
+	});
+}
+
+#[test]
+fn solution_is_revealed_once_the_quiz_expired() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert!(matches!(QuizModule::reveal_solution(quiz), Err(Error::<Test>::QuizStillActive)));
+		run_to_block(11);
+		assert_eq!(QuizModule::reveal_solution(quiz).ok(), Some(key()));
+	});
+}
+
+#[test]
+fn translations_line_up_with_the_quiz() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let german: Vec<Question> = questions()
+			.into_iter()
+			.map(|question| Question { statement: [&b"de "[..], &question.statement[..]].concat(), lang: Some(*b"de"), ..question })
+			.collect();
+		assert_noop!(
+			QuizModule::add_quiz_translation(Origin::signed(ALICE), quiz, LANG, german.clone()),
+			Error::<Test>::LanguageMismatch
+		);
+		assert_noop!(
+			QuizModule::add_quiz_translation(Origin::signed(ALICE), quiz, *b"fr", german.clone()),
+			Error::<Test>::LanguageMismatch
+		);
+		assert_noop!(
+			QuizModule::add_quiz_translation(Origin::signed(ALICE), quiz, *b"de", german[..4].to_vec()),
+			Error::<Test>::QuestionCountMismatch
+		);
+		assert_noop!(
+			QuizModule::add_quiz_translation(Origin::signed(BOB), quiz, *b"de", german.clone()),
+			Error::<Test>::NotTheQuizOwner
+		);
+
+		assert_ok!(QuizModule::add_quiz_translation(Origin::signed(ALICE), quiz, *b"de", german.clone()));
+		assert_eq!(QuizModule::get_quiz_lang(quiz, *b"de"), Some(german));
+		assert_eq!(QuizModule::get_quiz_lang(quiz, LANG), Some(questions()));
+		assert_eq!(QuizModule::get_quiz_lang(quiz, *b"fr"), None);
+		// every language is scored against the one answer key
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(score(BOB, quiz), 5);
+	});
+}
+
+#[test]
+fn rejected_attempt_only_pays_for_its_reads() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let error = QuizModule::attempt_quiz(Origin::signed(ALICE), quiz, key(), None).unwrap_err();
+		assert_eq!(error.error, Error::<Test>::OwnerCannotAttemptQuiz.into());
+		assert_eq!(error.post_info.actual_weight, Some(QuizModule::rejected_attempt_weight()));
+		assert!(QuizModule::rejected_attempt_weight() < QuizModule::attempt_quiz_weight());
+	});
+}
+
+#[test]
+fn active_quizzes_are_capped() {
+	new_test_ext().execute_with(|| {
+		MaxActiveQuizzes::set(2);
+		let first = add_quiz(ALICE, 0);
+		add_quiz(BOB, 0);
+		assert_noop!(create(CHARLIE, questions(), key(), 0), Error::<Test>::TooManyActiveQuizzes);
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), first));
+		assert_ok!(create(CHARLIE, questions(), key(), 0));
+	});
+}
+
+#[test]
+fn shuffled_options_are_stable_and_scored_as_displayed() {
+	new_test_ext().execute_with(|| {
+		ShuffleOptions::set(true);
+		let quiz = add_quiz(ALICE, 0);
+		let order = QuizModule::option_order(quiz, &BOB).unwrap();
+		assert_eq!(order.len(), QUESTIONS_PER_QUIZ);
+		assert_eq!(QuizModule::option_order(quiz, &BOB), Some(order.clone()));
+
+		assert_ok!(attempt(BOB, quiz, displayed(&order, key())));
+		assert_eq!(score(BOB, quiz), 5);
+	});
+}
+
+#[test]
+fn every_option_is_shown_first_about_as_often() {
+	let mut first = [0u32; OPTIONS_PER_QUESTION as usize];
+	for who in 0..2_400u64 {
+		let order = QuizModule::option_permutation(&quiz_id(1), &who, None, 0);
+		let mut sorted = order;
+		sorted.sort_unstable();
+		assert_eq!(sorted, [0, 1, 2, 3]);
+		first[order[0] as usize] += 1;
+	}
+	// 600 each when uniform
+	assert!(first.iter().all(|count| (500..700).contains(count)), "{:?}", first);
+}
+
+#[test]
+fn started_attempts_fix_their_own_option_order() {
+	new_test_ext().execute_with(|| {
+		ShuffleOptions::set(true);
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		let first = QuizModule::option_order(quiz, &BOB).unwrap();
+		assert_ok!(QuizModule::cancel_attempt(Origin::signed(BOB), quiz));
+
+		// cancelling and starting again does not reroll the order
+		System::set_block_number(2);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_eq!(QuizModule::option_order(quiz, &BOB), Some(first.clone()));
+
+		// the order shown in the start block is the order scored, however late the submission
+		System::set_block_number(5);
+		assert_ok!(attempt(BOB, quiz, displayed(&first, key())));
+		assert_eq!(score(BOB, quiz), 5);
+
+		// only a submitted attempt lets the next one be shuffled anew
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_ne!(QuizModule::option_order(quiz, &BOB), Some(first));
+	});
+}
+
+#[test]
+fn cancelled_attempts_draw_the_same_questions_again() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let id = quiz_id(quiz);
+		let (pooled, answers) = pool(8);
+		assert_ok!(QuizModule::set_question_pool(Origin::signed(ALICE), quiz, pooled, answers, QUESTIONS_PER_QUIZ as u32));
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		let drawn = QuizModule::get_drawn_questions(&id, &BOB);
+		assert!(drawn.is_some());
+
+		assert_ok!(QuizModule::cancel_attempt(Origin::signed(BOB), quiz));
+		assert_eq!(QuizModule::get_drawn_questions(&id, &BOB), None);
+		System::set_block_number(2);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_eq!(QuizModule::get_drawn_questions(&id, &BOB), drawn);
+	});
+}
+
+#[test]
+fn submission_has_to_answer_the_questions_of_the_quiz() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		// every solution holds five answers, a quiz left with four questions cannot take them
+		Quizzes::<Test>::mutate(quiz_id(quiz), |stored| {
+			stored.as_mut().unwrap().questions.pop();
+		});
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::AnswerCountMismatch);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &BOB, key(), None), Err(AttemptStatus::AnswerCountMismatch));
+	});
+}
+
+#[test]
+fn answer_keys_need_one_answer_per_question() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(
+			QuizModule::validate_solution(&key(), &questions()[..4]),
+			Err(Error::<Test>::SolutionShapeMismatch)
+		));
+		assert!(QuizModule::validate_solution(&key(), &questions()).is_ok());
+	});
+}
+
+#[test]
+fn stake_is_returned_on_a_pass_and_forfeited_on_a_fail() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		assert_noop!(QuizModule::set_quiz_stake(Origin::signed(BOB), quiz, 100, 3), Error::<Test>::NotTheQuizOwner);
+		assert_ok!(QuizModule::set_quiz_stake(Origin::signed(ALICE), quiz, 100, 3));
+
+		// the stake replaces the per wrong answer charge
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert!(has_event(QuizEvent::StakeSettled(quiz, BOB, 100, true)));
+
+		assert_ok!(attempt(CHARLIE, quiz, submission(2)));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 100);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit + 100);
+		assert!(has_event(QuizEvent::StakeSettled(quiz, CHARLIE, 100, false)));
+
+		assert_noop!(QuizModule::set_quiz_stake(Origin::signed(ALICE), quiz, 0, 0), Error::<Test>::QuizAlreadyAttempted);
+	});
+}
+
+#[test]
+fn linked_accounts_are_blocked_per_quiz() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let other = add_quiz(ALICE, 0);
+		assert_noop!(
+			QuizModule::block_linked_account(Origin::signed(ALICE), quiz, BOB),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(QuizModule::block_linked_account(Origin::root(), quiz, BOB));
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::LinkedAccountBlocked);
+		assert_noop!(QuizModule::start_attempt(Origin::signed(BOB), quiz), Error::<Test>::LinkedAccountBlocked);
+		assert_ok!(attempt(BOB, other, key()));
+
+		assert_ok!(QuizModule::unblock_linked_account(Origin::root(), quiz, BOB));
+		assert_ok!(attempt(BOB, quiz, key()));
+	});
+}
+
+#[test]
+fn token_costs_round_as_configured() {
+	new_test_ext().execute_with(|| {
+		let tenth = Perbill::from_percent(10);
+		for (rounding, expected) in [(CostRounding::Down, [1, 1]), (CostRounding::Up, [2, 2]), (CostRounding::Nearest, [1, 2])] {
+			TokenCostRounding::set(rounding);
+			assert_eq!([QuizModule::scale_cost(14, tenth), QuizModule::scale_cost(16, tenth)], expected);
+			// exact amounts are never rounded
+			assert_eq!(QuizModule::scale_cost(20, tenth), 2);
+		}
+	});
+}
+
+#[test]
+fn lifecycle_events_carry_the_quiz_count() {
+	new_test_ext().execute_with(|| {
+		let expiring = add_quiz(ALICE, 2);
+		assert!(has_event(QuizEvent::QuizCreated(expiring, ALICE, 2)));
+		let deleted = add_quiz(ALICE, 0);
+		let forced = add_quiz(ALICE, 0);
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), deleted));
+		assert!(has_event(QuizEvent::QuizDeleted(deleted, ALICE, 1)));
+		assert_ok!(QuizModule::force_expire_quiz(Origin::root(), forced, false));
+		assert!(has_event(QuizEvent::QuizForceExpired(forced)));
+		run_to_block(11);
+		assert!(has_event(QuizEvent::QuizExpired(expiring, 11)));
+	});
+}
+
+#[test]
+fn answer_keys_pick_an_option_for_every_question() {
+	new_test_ext().execute_with(|| {
+		for position in 0..QUESTIONS_PER_QUIZ {
+			let abstaining = with_answer(key(), position, Answer::Abstain);
+			assert_noop!(create(ALICE, questions(), abstaining, 0), Error::<Test>::InvalidOptionProvided);
+			// a set of options only keys a multi-select question
+			let several = with_answer(key(), position, Answer::Several(0b0011));
+			assert_noop!(create(ALICE, questions(), several, 0), Error::<Test>::InvalidOptionProvided);
+		}
+	});
+}
+
+#[test]
+fn stretch_attempts_cost_more_and_stop_at_the_margin() {
+	new_test_ext().execute_with(|| {
+		let matching = add_quiz(ALICE, 1);
+		let stretch = add_quiz(ALICE, 2);
+		let beyond = add_quiz(ALICE, 3);
+
+		// unrated players meet the requirement of a quiz rated 1
+		assert_ok!(attempt(BOB, matching, submission(4)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - WRONG_ANSWER);
+		// and are one point short of a quiz rated 2
+		assert_ok!(attempt(CHARLIE, stretch, submission(4)));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - WRONG_ANSWER * StretchMultiplier::get() as u64);
+		assert_noop!(attempt(DAVE, beyond, key()), Error::<Test>::UserRatingTooLow);
+	});
+}
+
+#[test]
+fn edits_bump_the_version_and_keep_recorded_results() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		let result_version = |who: u64| QuizModule::get_attempt_result(&quiz_id(quiz), &who).unwrap().version;
+		assert_eq!(result_version(BOB), 0);
+
+		let edited: Vec<Question> = questions()
+			.into_iter()
+			.map(|question| Question { statement: [&question.statement[..], &b" (edited)"[..]].concat(), ..question })
+			.collect();
+		assert_noop!(
+			QuizModule::edit_quiz(Origin::signed(BOB), quiz, edited.clone(), key()),
+			Error::<Test>::NotTheQuizOwner
+		);
+		assert_ok!(QuizModule::edit_quiz(Origin::signed(ALICE), quiz, edited.clone(), key()));
+		assert!(has_event(QuizEvent::QuizEdited(quiz, 1)));
+		assert_eq!(QuizModule::quiz_questions(quiz), Some(edited));
+		assert_eq!(result_version(BOB), 0);
+
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert_eq!(result_version(CHARLIE), 1);
+	});
+}
+
+#[test]
+fn attempted_quiz_is_only_edited_with_its_answer_key() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_noop!(
+			QuizModule::edit_quiz(Origin::signed(ALICE), quiz, questions(), submission(3)),
+			Error::<Test>::QuizAlreadyAttempted
+		);
+		assert_ok!(QuizModule::edit_quiz(Origin::signed(ALICE), quiz, questions(), key()));
+		assert_eq!(QuizModule::get_solution(&quiz_id(quiz)), Some(key()));
+	});
+}
+
+#[test]
+fn owner_updates_the_solution_until_the_first_attempt() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let corrected = submission(3);
+		assert_noop!(
+			QuizModule::update_solution(Origin::signed(BOB), quiz, corrected.clone()),
+			Error::<Test>::NotTheQuizOwner
+		);
+		assert_ok!(QuizModule::update_solution(Origin::signed(ALICE), quiz, corrected.clone()));
+		assert_eq!(QuizModule::get_solution(&quiz_id(quiz)), Some(corrected.clone()));
+		assert_eq!(QuizModule::quiz_questions(quiz), Some(questions()));
+		assert!(has_event(QuizEvent::SolutionUpdated(quiz)));
+
+		assert_ok!(attempt(BOB, quiz, corrected));
+		assert_eq!(score(BOB, quiz), 5);
+		assert_noop!(
+			QuizModule::update_solution(Origin::signed(ALICE), quiz, key()),
+			Error::<Test>::QuizAlreadyAttempted
+		);
+	});
+}
+
+#[test]
+fn players_rate_quizzes_they_attempted() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::quality_average(quiz), None);
+		assert_noop!(QuizModule::rate_quiz(Origin::signed(BOB), quiz, 4), Error::<Test>::MustAttemptFirst);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert_noop!(QuizModule::rate_quiz(Origin::signed(BOB), quiz, 0), Error::<Test>::InvalidStars);
+		assert_noop!(QuizModule::rate_quiz(Origin::signed(BOB), quiz, MAX_STARS + 1), Error::<Test>::InvalidStars);
+
+		assert_ok!(QuizModule::rate_quiz(Origin::signed(BOB), quiz, 4));
+		assert_ok!(QuizModule::rate_quiz(Origin::signed(CHARLIE), quiz, 2));
+		assert_eq!(QuizModule::quality_average(quiz), Some(Perbill::from_percent(60)));
+		// rating again replaces the earlier rating
+		assert_ok!(QuizModule::rate_quiz(Origin::signed(BOB), quiz, 5));
+		assert_eq!(QuizModule::quality_average(quiz), Some(Perbill::from_percent(70)));
+	});
+}
+
+#[test]
+fn consecutive_pages_list_every_quiz_once() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..5 {
+			add_quiz(ALICE, 0);
+		}
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), 3));
+
+		let mut listed = Vec::new();
+		let mut cursor = None;
+		for _ in 0..10 {
+			let (page, next) = QuizModule::list_quizzes(cursor, 2);
+			listed.extend(page.into_iter().map(|(quiz_count, _)| quiz_count));
+			cursor = next;
+			if cursor.is_none() {
+				break;
+			}
+		}
+		assert_eq!(cursor, None);
+		assert_eq!(listed, vec![1, 2, 4, 5]);
+	});
+}
+
+#[test]
+fn quizzes_are_indexed_in_creation_order() {
+	new_test_ext().execute_with(|| {
+		let counts: Vec<u64> = (0..3).map(|_| add_quiz(ALICE, 0)).collect();
+		for quiz_count in &counts {
+			assert_eq!(QuizModule::get_quiz_by_index(quiz_count), Some(quiz_id(*quiz_count)));
+		}
+		let (page, _) = QuizModule::list_quizzes(None, 10);
+		assert_eq!(page.into_iter().map(|(quiz_count, _)| quiz_count).collect::<Vec<_>>(), counts);
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), counts[1]));
+		assert_eq!(QuizModule::get_quiz_by_index(counts[1]), None);
+	});
+}
+
+#[test]
+fn neighbouring_answers_earn_partial_credit_on_ordinal_questions() {
+	new_test_ext().execute_with(|| {
+		let mut ordinal = questions();
+		ordinal[0].ordinal = true;
+		assert_ok!(create(ALICE, ordinal.clone(), key(), 0));
+		let id = quiz_id(QuizModule::get_latest_quiz());
+
+		let near = with_answer(key(), 0, Answer::Option2);
+		assert_eq!(QuizModule::score_submission(&id, &ordinal, &BOB, None, near, key()), (4, 450, 0b11110));
+		let far = with_answer(key(), 0, Answer::Option3);
+		assert_eq!(QuizModule::score_submission(&id, &ordinal, &BOB, None, far, key()), (4, 400, 0b11110));
+		// a neighbouring answer on any other question is just wrong
+		let plain = with_answer(key(), 1, Answer::Option3);
+		assert_eq!(QuizModule::score_submission(&id, &ordinal, &BOB, None, plain, key()).1, 400);
+	});
+}
+
+#[test]
+fn multi_select_questions_credit_exact_and_partial_picks() {
+	new_test_ext().execute_with(|| {
+		let mut multi = questions();
+		multi[0].multi_select = true;
+		let solution = with_answer(key(), 0, Answer::Several(0b0011));
+		assert_ok!(create(ALICE, multi.clone(), solution.clone(), 0));
+		let id = quiz_id(QuizModule::get_latest_quiz());
+		let scored = |submission| QuizModule::score_submission(&id, &multi, &BOB, None, submission, solution.clone()).1;
+
+		assert_eq!(scored(solution.clone()), 500);
+		let partial = with_answer(solution.clone(), 0, Answer::Several(0b0001));
+		let wrong = with_answer(solution.clone(), 0, Answer::Several(0b0101));
+		assert_eq!(scored(partial.clone()), 400);
+		MultiSelectPartialCredit::set(Perbill::from_percent(50));
+		assert_eq!(scored(partial), 450);
+		// picking an option that is not correct earns nothing
+		assert_eq!(scored(wrong), 400);
+	});
+}
+
+#[test]
+fn attempt_fee_is_due_on_every_attempt() {
+	new_test_ext().execute_with(|| {
+		AttemptFee::set(7);
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 7);
+		assert_eq!(QuizModule::quiz_terms(quiz).unwrap().attempt_fee, 7);
+
+		fund(POOR, 5);
+		assert_noop!(attempt(POOR, quiz, key()), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn try_state_finds_inconsistent_storage() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::do_try_state(), Ok(()));
+		Solutions::<Test>::remove(quiz_id(quiz));
+		assert_eq!(QuizModule::do_try_state(), Err("quiz without a solution"));
+	});
+}
+
+#[test]
+fn quiz_rating_is_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create(ALICE, questions(), key(), MaxRating::get()));
+		assert_noop!(create(ALICE, questions(), key(), MaxRating::get() + 1), Error::<Test>::RatingTooHighForQuiz);
+	});
+}
+
+#[test]
+fn explanations_are_only_handed_to_players_who_attempted() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let explanations: Vec<BoundedVec<u8, MaxTextLen>> =
+			(0..QUESTIONS_PER_QUIZ).map(|_| b"because".to_vec().try_into().unwrap()).collect();
+		assert_noop!(
+			QuizModule::set_explanations(Origin::signed(ALICE), quiz, explanations[..4].to_vec()),
+			Error::<Test>::QuestionCountMismatch
+		);
+		assert_ok!(QuizModule::set_explanations(Origin::signed(ALICE), quiz, explanations.clone()));
+		assert_eq!(QuizModule::explanations_for(quiz, &BOB), None);
+
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(QuizModule::explanations_for(quiz, &BOB), Some(explanations));
+	});
+}
+
+#[test]
+fn dry_run_matches_the_attempt_and_writes_nothing() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &BOB, submission(3), None), Ok(3));
+		assert_eq!(QuizModule::attempt_count(quiz), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_eq!(score(BOB, quiz), 3);
+
+		assert_eq!(QuizModule::dry_run_attempt(quiz + 1, &BOB, key(), None), Err(AttemptStatus::QuizDoesNotExist));
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &ALICE, key(), None), Err(AttemptStatus::OwnerCannotAttemptQuiz));
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &CHARLIE, key(), Some(CHARLIE)), Err(AttemptStatus::SelfReferral));
+		fund(POOR, 5);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &POOR, submission(0), None), Err(AttemptStatus::InsufficientBalance));
+	});
+}
+
+#[test]
+fn earnings_are_claimable_once_their_lock_elapsed() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+
+		// locked for `EarningsLockPeriod` blocks rounded up to the next whole period
+		let unlock_at = 20;
+		assert_eq!(QuizModule::get_earnings_locks(ALICE), vec![(unlock_at, 2 * WRONG_ANSWER)]);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit + 2 * WRONG_ANSWER);
+		assert_noop!(QuizModule::claim_earnings(Origin::signed(ALICE)), Error::<Test>::NoEarningsToClaim);
+
+		System::set_block_number(unlock_at);
+		assert_ok!(QuizModule::claim_earnings(Origin::signed(ALICE)));
+		assert!(has_event(QuizEvent::EarningsClaimed(ALICE, 2 * WRONG_ANSWER)));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit);
+		assert!(QuizModule::get_earnings_locks(ALICE).is_empty());
+	});
+}
+
+#[test]
+fn earnings_locks_stay_bounded() {
+	new_test_ext().execute_with(|| {
+		let mut locks = Vec::new();
+		// an owner earning every block and never claiming
+		for now in 1..=50u64 {
+			let unlock_at = QuizModule::lock_boundary(now + EarningsLockPeriod::get());
+			QuizModule::add_earnings_lock(&mut locks, unlock_at, 1, now);
+			assert!(locks.len() <= 3);
+		}
+		assert_eq!(locks.iter().map(|(_, amount)| amount).sum::<u64>(), 50);
+	});
+}
+
+#[test]
+fn cloned_quiz_is_independent_of_its_source() {
+	new_test_ext().execute_with(|| {
+		let source = add_quiz(ALICE, 1);
+		assert_noop!(QuizModule::clone_quiz(Origin::signed(BOB), source), Error::<Test>::NotTheQuizOwner);
+		assert_ok!(QuizModule::clone_quiz(Origin::signed(ALICE), source));
+		let clone = QuizModule::get_latest_quiz();
+		assert_ne!(clone, source);
+		assert!(has_event(QuizEvent::QuizCloned(source, clone, ALICE)));
+		assert_eq!(QuizModule::quiz_questions(clone), QuizModule::quiz_questions(source));
+		assert_eq!(QuizModule::get_solution(&quiz_id(clone)), Some(key()));
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), source));
+		assert_eq!(QuizModule::quiz_questions(clone), Some(questions()));
+	});
+}
+
+#[test]
+fn players_of_the_source_cannot_attempt_its_clone() {
+	new_test_ext().execute_with(|| {
+		let source = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, source, key()));
+		assert_ok!(QuizModule::clone_quiz(Origin::signed(ALICE), source));
+		let clone = QuizModule::get_latest_quiz();
+		assert_noop!(attempt(BOB, clone, key()), Error::<Test>::AlreadyAttemptedSource);
+		assert_ok!(attempt(CHARLIE, clone, key()));
+	});
+}
+
+#[test]
+fn attempts_are_scored_on_the_questions_drawn_from_the_pool() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let id = quiz_id(quiz);
+		let (pooled, answers) = pool(8);
+		let set_pool = |size: usize, draw: u32| {
+			QuizModule::set_question_pool(Origin::signed(ALICE), quiz, pooled[..size].to_vec(), answers[..size].to_vec(), draw)
+		};
+		assert_noop!(set_pool(8, 0), Error::<Test>::InvalidDrawCount);
+		assert_noop!(set_pool(8, QUESTIONS_PER_QUIZ as u32 + 1), Error::<Test>::InvalidDrawCount);
+		assert_noop!(set_pool(2, 3), Error::<Test>::PoolTooSmall);
+		assert_ok!(set_pool(8, 3));
+		assert!(has_event(QuizEvent::QuestionPoolSet(quiz, 8, 3)));
+
+		// the questions are only known once drawn when the attempt starts
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::NoAttemptInProgress);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_ok!(QuizModule::start_attempt(Origin::signed(CHARLIE), quiz));
+		let drawn = QuizModule::get_drawn_questions(&id, &BOB).unwrap();
+		let mut distinct = drawn.clone();
+		distinct.sort_unstable();
+		distinct.dedup();
+		assert_eq!(distinct.len(), 3);
+		assert_ne!(Some(drawn.clone()), QuizModule::get_drawn_questions(&id, &CHARLIE));
+		assert_eq!(QuizModule::option_order(quiz, &BOB).map(|order| order.len()), Some(3));
+
+		let mut drawn_key = [Answer::Abstain; QUESTIONS_PER_QUIZ];
+		for (answer, index) in drawn_key.iter_mut().zip(&drawn) {
+			*answer = answers[*index as usize];
+		}
+		// only the drawn questions can be answered
+		let mut overanswered = drawn_key;
+		overanswered[3] = answers[0];
+		assert_noop!(attempt(BOB, quiz, Solution::from_answers(overanswered)), Error::<Test>::AnswerCountMismatch);
+
+		// a perfect score out of the three drawn costs nothing, the questions not drawn included
+		assert_ok!(attempt(BOB, quiz, Solution::from_answers(drawn_key)));
+		assert_eq!(score(BOB, quiz), 3);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(perfect_scores(), vec![(BOB, quiz)]);
+	});
+}
+
+#[test]
+fn opted_out_players_only_attempt_free_quizzes() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::toggle_monetization_opt_out(Origin::signed(BOB)));
+		assert!(has_event(QuizEvent::MonetizationOptOutSet(BOB, true)));
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::MonetizationOptedOut);
+
+		// without a cost per wrong answer nothing is at risk
+		TokenUnit::set(0);
+		assert_ok!(attempt(BOB, quiz, submission(2)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn leaderboard_does_not_depend_on_the_order_players_were_added() {
+	let board = |order: [u64; 4]| {
+		new_test_ext().execute_with(|| {
+			for who in order {
+				QuizModule::update_leaderboard(who, if who == EVE { 4 } else { 3 });
+			}
+			QuizModule::get_leaderboard()
+		})
+	};
+	let first = board([BOB, CHARLIE, DAVE, EVE]);
+	assert_eq!(first, board([EVE, DAVE, CHARLIE, BOB]));
+	assert_eq!(first, board([CHARLIE, EVE, BOB, DAVE]));
+	assert_eq!(first.iter().map(|entry| entry.account).collect::<Vec<_>>(), vec![EVE, BOB, CHARLIE, DAVE]);
+}
+
+#[test]
+fn call_weights_cover_the_work_done() {
+	new_test_ext().execute_with(|| {
+		assert!(QuizModule::attempt_quiz_weight() > 0);
+		assert!(QuizModule::create_quiz_weight(QUESTIONS_PER_QUIZ) > QuizModule::create_quiz_weight(1));
+		let batch = |size: usize| crate::Call::<Test>::add_quizzes { quizzes: vec![payload(); size] }.get_dispatch_info().weight;
+		assert!(batch(2) > batch(1));
+		let translation = |size: usize| {
+			crate::Call::<Test>::add_quiz_translation { quiz_count: 1, lang: *b"de", questions: questions()[..size].to_vec() }
+				.get_dispatch_info()
+				.weight
+		};
+		assert!(translation(QUESTIONS_PER_QUIZ) > translation(1));
+	});
+}
+
+#[test]
+fn completion_hook_is_told_every_scored_attempt() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_noop!(attempt(ALICE, quiz, key()), Error::<Test>::OwnerCannotAttemptQuiz);
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert_eq!(completed_attempts(), vec![(BOB, quiz, 3), (CHARLIE, quiz, 5)]);
+	});
+}
+
+#[test]
+fn perfect_score_hook_only_fires_on_a_perfect_score() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(attempt(CHARLIE, quiz, submission(4)));
+		assert_eq!(perfect_scores(), vec![(BOB, quiz)]);
+	});
+}
+
+#[test]
+fn quiz_creation_never_overwrites_a_stored_quiz() {
+	new_test_ext().execute_with(|| {
+		let squatter = Quiz::<Test> {
+			count: 1,
+			owner: BOB,
+			questions: questions(),
+			lang: LANG,
+			rating: 0,
+			deposit: 0,
+			expires_at: 100,
+			stake: 0,
+			pass_threshold: 0,
+			version: 0,
+			max_attempts_per_user: 0,
+		};
+		Quizzes::<Test>::insert(quiz_id(1), squatter.clone());
+		assert_noop!(create(ALICE, questions(), key(), 0), Error::<Test>::QuizIdCollision);
+		assert_eq!(QuizModule::get_quiz(&quiz_id(1)), Some(squatter));
+	});
+}
+
+#[test]
+fn referrer_takes_a_cut_of_the_charge() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		assert_ok!(attempt_referred(BOB, quiz, submission(3), Some(DAVE)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 2 * WRONG_ANSWER);
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 2);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit + 18);
+		assert!(has_event(QuizEvent::TokensCharged(quiz, BOB, DAVE, 2)));
+		assert!(has_event(QuizEvent::TokensCharged(quiz, BOB, ALICE, 18)));
+
+		assert_noop!(attempt_referred(CHARLIE, quiz, key(), Some(CHARLIE)), Error::<Test>::SelfReferral);
+	});
+}
+
+#[test]
+fn referral_cut_below_the_existential_deposit_stays_with_the_owner() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(5);
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		assert_ok!(attempt_referred(BOB, quiz, submission(3), Some(NEWCOMER)));
+		assert_eq!(Balances::free_balance(NEWCOMER), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit + 2 * WRONG_ANSWER);
+	});
+}
+
+#[test]
+fn quiz_terms_are_what_an_attempt_is_charged() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let terms = QuizModule::quiz_terms(quiz).unwrap();
+		assert_eq!(terms.cost_per_wrong_answer, WRONG_ANSWER);
+		assert_eq!(terms.max_charge, 5 * WRONG_ANSWER);
+		assert_eq!((terms.stake, terms.prize_pool), (0, 0));
+
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 2 * terms.cost_per_wrong_answer);
+		assert!(QuizModule::quiz_terms(quiz + 1).is_none());
+	});
+}
+
+#[test]
+fn only_approved_creators_publish_quizzes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(create(OUTSIDER, questions(), key(), 0), DispatchError::BadOrigin);
+		assert_noop!(
+			QuizModule::add_quizzes(Origin::signed(OUTSIDER), vec![payload()]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(create(ALICE, questions(), key(), 0));
+	});
+}
+
+#[test]
+fn attempt_costs_saturate_instead_of_overflowing() {
+	new_test_ext().execute_with(|| {
+		TokenUnit::set(u64::MAX / 2);
+		RatingCostFactor::set(Perbill::from_percent(50));
+		assert_eq!(QuizModule::attempt_cost(0, MaxRating::get()), u64::MAX);
+		assert_eq!(QuizModule::charge_for(0, true, MaxRating::get()), u64::MAX);
+		assert_eq!(QuizModule::attempt_cost(4, 0), u64::MAX / 2);
+		assert_eq!(QuizModule::attempt_cost(QUESTIONS_PER_QUIZ as u8, MaxRating::get()), 0);
+		// more correct answers than questions does not underflow
+		assert_eq!(QuizModule::attempt_cost(u8::MAX, MaxRating::get()), 0);
+	});
+}
+
+#[test]
+fn transfer_events_report_the_charge() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert!(has_event(QuizEvent::TokensCharged(quiz, BOB, ALICE, QuizModule::attempt_cost(3, 0))));
+
+		// a perfect attempt moves no tokens and reports no transfer
+		System::reset_events();
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert!(!quiz_events().iter().any(|event| matches!(event, QuizEvent::TokensCharged(..))));
+	});
+}
+
+#[test]
+fn failed_batch_creates_none_of_its_quizzes() {
+	new_test_ext().execute_with(|| {
+		// enough for the first deposit of the batch only
+		let deposit = QuizModule::quiz_deposit(&questions());
+		fund(POOR, deposit + deposit / 2);
+		assert_noop!(
+			QuizModule::add_quizzes(Origin::signed(POOR), vec![payload(); 2]),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(QuizModule::get_latest_quiz(), 0);
+		assert_eq!(Balances::reserved_balance(POOR), 0);
+		assert!(QuizModule::scheduled_deletions(11).is_empty());
+	});
+}
+
+#[test]
+fn quizzes_are_created_in_batches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuizModule::add_quizzes(Origin::signed(ALICE), vec![payload(); 3]));
+		assert_eq!(last_quiz_event(), QuizEvent::QuizzesCreated(ALICE, vec![1, 2, 3]));
+		assert_noop!(
+			QuizModule::add_quizzes(Origin::signed(ALICE), vec![payload(); 4]),
+			Error::<Test>::BatchTooLarge
+		);
+		// one invalid quiz rejects the whole batch
+		let invalid = QuizPayload { rating: MaxRating::get() + 1, ..payload() };
+		assert_noop!(
+			QuizModule::add_quizzes(Origin::signed(ALICE), vec![payload(), invalid]),
+			Error::<Test>::RatingTooHighForQuiz
+		);
+		assert_eq!(QuizModule::get_active_quizzes(), 3);
+	});
+}
+
+#[test]
+fn prize_pool_is_split_by_score_among_the_winners() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::fund_prize_pool(Origin::signed(DAVE), quiz, 100));
+		assert_ok!(attempt(BOB, quiz, submission(4)));
+		assert_ok!(attempt(CHARLIE, quiz, submission(1)));
+		assert_ok!(attempt(EVE, quiz, submission(0)));
+		let before = [BOB, CHARLIE, EVE].map(Balances::free_balance);
+
+		run_to_block(11);
+		assert!(has_event(QuizEvent::TournamentSettled(quiz, vec![(BOB, 80), (CHARLIE, 20)])));
+		assert_eq!([BOB, CHARLIE, EVE].map(Balances::free_balance), [before[0] + 80, before[1] + 20, before[2]]);
+	});
+}
+
+#[test]
+fn prize_pool_nobody_competed_for_goes_back_to_its_funder() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::fund_prize_pool(Origin::signed(DAVE), quiz, 100));
+		assert_eq!(QuizModule::quiz_terms(quiz).unwrap().prize_pool, 100);
+		run_to_block(11);
+		assert!(has_event(QuizEvent::PrizeRefunded(quiz, DAVE, 100)));
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn large_prizes_are_vested_and_small_ones_paid_liquid() {
+	new_test_ext().execute_with(|| {
+		let small = add_quiz(ALICE, 0);
+		let large = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::fund_prize_pool(Origin::signed(DAVE), small, 100));
+		assert_ok!(QuizModule::fund_prize_pool(Origin::signed(DAVE), large, 5_000));
+		assert_ok!(attempt(BOB, small, key()));
+		assert_ok!(attempt(CHARLIE, large, key()));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 100);
+		assert_eq!(vesting_schedules(), vec![(CHARLIE, 5_000, 5_000 / VestingPeriod::get(), 11)]);
+		assert!(has_event(QuizEvent::PrizeVested(large, CHARLIE, 5_000)));
+		assert!(!has_event(QuizEvent::PrizeVested(small, BOB, 100)));
+	});
+}
+
+#[test]
+fn receipt_reports_the_attempt() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::attempt_quiz_with_receipt(Origin::signed(BOB), quiz, submission(3), None));
+		let receipt = AttemptReceipt { score: 3, max_score: 5, charged: 2 * WRONG_ANSWER, new_rating: 3 };
+		assert_eq!(last_quiz_event(), QuizEvent::AttemptReceiptIssued(quiz, BOB, receipt.clone()));
+		assert_eq!(AttemptReceipt::<u64>::decode(&mut &receipt.encode()[..]).ok(), Some(receipt));
+
+		// a rejection is charged the same weight whichever call it came through
+		let via_receipt = QuizModule::attempt_quiz_with_receipt(Origin::signed(ALICE), quiz, key(), None).unwrap_err();
+		let via_attempt = QuizModule::attempt_quiz(Origin::signed(ALICE), quiz, key(), None).unwrap_err();
+		assert_eq!(via_receipt, via_attempt);
+		assert_eq!(via_receipt.post_info.actual_weight, Some(QuizModule::rejected_attempt_weight()));
+	});
+}
+
+#[test]
+fn improving_clears_the_cooldown_when_configured() {
+	new_test_ext().execute_with(|| {
+		AttemptCooldown::set(5);
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::CooldownActive);
+
+		ResetCooldownOnImprovement::set(true);
+		assert_ok!(attempt(CHARLIE, quiz, submission(3)));
+		// rising from unrated to 3 cleared the cooldown, dropping to 2 does not
+		assert_ok!(attempt(CHARLIE, quiz, submission(1)));
+		assert_noop!(attempt(CHARLIE, quiz, key()), Error::<Test>::CooldownActive);
+
+		System::set_block_number(1 + 5);
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+	});
+}
+
+#[test]
+fn provisional_players_are_gated_as_unrated() {
+	new_test_ext().execute_with(|| {
+		MinAttemptsForRating::set(3);
+		let quiz = add_quiz(ALICE, 3);
+		// rated 5, but on fewer attempts than the rating needs to count
+		QuizModule::set_user_rating(&BOB, rating::from_points(5), None);
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::UserRatingTooLow);
+
+		UserAttempts::<Test>::insert(BOB, 3);
+		assert_ok!(attempt(BOB, quiz, key()));
+	});
+}
+
+#[test]
+fn every_milestone_crossed_is_reported() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let milestones = || {
+			quiz_events()
+				.into_iter()
+				.filter_map(|event| match event {
+					QuizEvent::RatingMilestone(BOB, milestone) => Some(milestone),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_eq!(milestones(), vec![1, 2, 3]);
+
+		System::reset_events();
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert!(milestones().is_empty());
+	});
+}
+
+#[test]
+fn higher_rated_quizzes_cost_more_for_the_same_score() {
+	new_test_ext().execute_with(|| {
+		RatingCostFactor::set(Perbill::from_percent(10));
+		let low = add_quiz(ALICE, 1);
+		let high = add_quiz(ALICE, 5);
+		assert_eq!(QuizModule::attempt_cost(3, 1), 22);
+		assert_eq!(QuizModule::attempt_cost(3, 5), 30);
+
+		assert_ok!(attempt(BOB, low, submission(3)));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 22);
+		QuizModule::set_user_rating(&CHARLIE, rating::from_points(5), None);
+		assert_ok!(attempt(CHARLIE, high, submission(3)));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 30);
+	});
+}
+
+#[test]
+fn governance_expires_a_quiz_the_schedule_lost() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		QuizModule::unschedule_deletion(&quiz_id(quiz), 11);
+		run_to_block(11);
+		assert!(QuizModule::get_quiz(&quiz_id(quiz)).is_some());
+
+		assert_noop!(QuizModule::force_expire_quiz(Origin::signed(ALICE), quiz, false), DispatchError::BadOrigin);
+		assert_ok!(QuizModule::force_expire_quiz(Origin::root(), quiz, false));
+		assert!(QuizModule::get_quiz(&quiz_id(quiz)).is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(QuizModule::reveal_solution(quiz).ok(), Some(key()));
+	});
+}
+
+#[test]
+fn defective_quiz_refunds_its_players() {
+	new_test_ext().execute_with(|| {
+		AttemptFee::set(7);
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		// the owner receives 18, 50 and 10, the fees and DAVE's referral cut of 2 go elsewhere
+		assert_ok!(attempt_referred(BOB, quiz, submission(3), Some(DAVE)));
+		assert_ok!(attempt(CHARLIE, quiz, submission(0)));
+		assert_ok!(attempt(EVE, quiz, submission(4)));
+		let earnings = 18 + 50 + 10;
+		assert_eq!(Balances::reserved_balance(ALICE), deposit + earnings);
+
+		assert_ok!(QuizModule::force_expire_quiz(Origin::root(), quiz, true));
+		// `MaxRefundsPerCall` players are refunded right away, anyone may continue with the rest
+		assert!(QuizModule::get_pending_refund(&quiz_id(quiz)).is_some());
+		assert_ok!(QuizModule::continue_refunds(Origin::signed(DAVE), quiz));
+		assert!(has_event(QuizEvent::RefundsCompleted(quiz)));
+		assert!(QuizModule::get_pending_refund(&quiz_id(quiz)).is_none());
+		assert!(has_event(QuizEvent::AttemptRefunded(quiz, BOB, 18)));
+		// everyone is left with the fee paid, BOB with the referral cut on top
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 7 - 2);
+		for who in [CHARLIE, EVE] {
+			assert_eq!(Balances::free_balance(who), INITIAL_BALANCE - 7);
+		}
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 2);
+		// the refunds came out of the deposit, the locked earnings are untouched
+		assert_eq!(Balances::reserved_balance(ALICE), earnings);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - earnings);
+		assert_noop!(QuizModule::continue_refunds(Origin::signed(DAVE), quiz), Error::<Test>::NoRefundsPending);
+	});
+}
+
+#[test]
+fn refunds_of_a_defective_quiz_stop_at_its_deposit() {
+	new_test_ext().execute_with(|| {
+		TokenUnit::set(100);
+		let quiz = add_quiz(ALICE, 0);
+		let deposit = Balances::reserved_balance(ALICE);
+		let other = add_quiz(ALICE, 0);
+		let reserved = Balances::reserved_balance(ALICE);
+		for who in [BOB, CHARLIE] {
+			assert_ok!(attempt(who, quiz, submission(0)));
+		}
+		// the owner received more than the deposit holds
+		assert!(2 * 500 > deposit);
+
+		assert_ok!(QuizModule::force_expire_quiz(Origin::root(), quiz, true));
+		assert!(has_event(QuizEvent::RefundsCompleted(quiz)));
+		let refunded = Balances::free_balance(BOB) + Balances::free_balance(CHARLIE) - 2 * (INITIAL_BALANCE - 500);
+		assert_eq!(refunded, deposit);
+		// neither the deposit of the other quiz nor the earnings locked on the owner paid for them
+		assert_eq!(Balances::reserved_balance(ALICE), reserved - deposit + 2 * 500);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - reserved);
+		assert!(QuizModule::get_quiz(&quiz_id(other)).is_some());
+	});
+}
+
+#[test]
+fn expiries_over_the_weight_budget_move_to_the_next_block() {
+	new_test_ext().execute_with(|| {
+		// room for a single expiry per block
+		ExpiryWeightBudget::set(QuizModule::expire_quiz_weight() * 3 / 2);
+		for _ in 0..3 {
+			add_quiz(ALICE, 0);
+		}
+		run_to_block(11);
+		assert_eq!(QuizModule::get_active_quizzes(), 2);
+		run_to_block(12);
+		assert_eq!(QuizModule::get_active_quizzes(), 1);
+		run_to_block(13);
+		assert_eq!(QuizModule::get_active_quizzes(), 0);
+	});
+}
+
+#[test]
+fn expiry_work_per_block_stays_within_the_budget() {
+	new_test_ext().execute_with(|| {
+		// room for two expiries per block, with seven quizzes due in block 11
+		let budget = QuizModule::expire_quiz_weight() * 5 / 2;
+		ExpiryWeightBudget::set(budget);
+		for _ in 0..7 {
+			add_quiz(ALICE, 0);
+		}
+		run_to_block(10);
+		let mut expired = Vec::new();
+		while QuizModule::get_active_quizzes() > 0 {
+			let next = System::block_number() + 1;
+			System::set_block_number(next);
+			assert!(QuizModule::on_initialize(next) <= budget);
+			// what did not fit is left in the schedule of block 11, not taken out of it
+			assert_eq!(QuizModule::scheduled_deletions(11).len() as u32, QuizModule::get_active_quizzes());
+			expired.push(QuizModule::get_active_quizzes());
+			assert!(next < 20);
+		}
+		assert_eq!(expired, vec![5, 3, 1, 0]);
+	});
+}
+
+#[test]
+fn quizzes_are_kept_for_attempts_started_before_they_expired() {
+	new_test_ext().execute_with(|| {
+		ExpiryGracePeriod::set(5);
+		let quiz = add_quiz(ALICE, 0);
+		run_to_block(9);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+
+		run_to_block(11);
+		assert!(QuizModule::get_quiz(&quiz_id(quiz)).is_some());
+		// new attempts are refused from the expiry on
+		assert_noop!(QuizModule::start_attempt(Origin::signed(CHARLIE), quiz), Error::<Test>::QuizExpiring);
+		assert_noop!(attempt(CHARLIE, quiz, key()), Error::<Test>::QuizExpiring);
+		assert_ok!(attempt(BOB, quiz, key()));
+
+		run_to_block(12);
+		assert!(QuizModule::get_quiz(&quiz_id(quiz)).is_none());
+	});
+}
+
+#[test]
+fn rank_counts_the_players_rated_higher() {
+	new_test_ext().execute_with(|| {
+		for (who, points) in [(BOB, 5), (CHARLIE, 3), (DAVE, 3), (EVE, 1)] {
+			QuizModule::set_user_rating(&who, rating::from_points(points), None);
+		}
+		assert_eq!(QuizModule::user_rank(&BOB), Some(1));
+		assert_eq!(QuizModule::user_rank(&CHARLIE), Some(2));
+		assert_eq!(QuizModule::user_rank(&DAVE), Some(2));
+		assert_eq!(QuizModule::user_rank(&EVE), Some(4));
+		assert_eq!(QuizModule::user_rank(&ALICE), None);
+	});
+}
+
+#[test]
+fn uniform_answer_keys_are_rejected_when_configured() {
+	new_test_ext().execute_with(|| {
+		let uniform = Solution::from_answers([Answer::Option1; QUESTIONS_PER_QUIZ]);
+		RejectUniformSolutions::set(true);
+		assert_noop!(create(ALICE, questions(), uniform.clone(), 0), Error::<Test>::SolutionTooUniform);
+		assert_ok!(create(ALICE, questions(), key(), 0));
+
+		RejectUniformSolutions::set(false);
+		assert_ok!(create(ALICE, questions(), uniform, 0));
+	});
+}
+
+#[test]
+fn governance_recomputes_a_rating_with_the_current_formula() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_ok!(attempt(BOB, quiz, submission(1)));
+		assert_eq!(QuizModule::get_score_history(BOB), vec![300, 100]);
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 200);
+
+		// with the whole weight on the latest score the rating is that score
+		RatingAlpha::set(Perbill::one());
+		assert_noop!(QuizModule::recompute_user_rating(Origin::signed(BOB), BOB), DispatchError::BadOrigin);
+		assert_ok!(QuizModule::recompute_user_rating(Origin::root(), BOB));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 100);
+		assert_eq!(last_quiz_event(), QuizEvent::RatingRecomputed(BOB, 1));
+		assert_noop!(QuizModule::recompute_user_rating(Origin::root(), CHARLIE), Error::<Test>::NoScoreHistory);
+	});
+}
+
+#[test]
+fn repeated_poor_attempts_lock_the_player_out() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		for _ in 0..MaxWrongAttempts::get() {
+			assert_ok!(attempt(BOB, quiz, submission(1)));
+		}
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::AttemptLimitReached);
+
+		// a good attempt in between starts the count over
+		for correct in [1, 1, 5, 1, 1] {
+			assert_ok!(attempt(CHARLIE, quiz, submission(correct)));
+		}
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+	});
+}
+
+#[test]
+fn owner_limits_the_attempts_per_player() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_noop!(QuizModule::set_attempt_limit(Origin::signed(BOB), quiz, 2), Error::<Test>::NotTheQuizOwner);
+		assert_ok!(QuizModule::set_attempt_limit(Origin::signed(ALICE), quiz, 2));
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::AttemptLimitReached);
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+
+		// once attempted the limit can only be raised or lifted
+		assert_noop!(QuizModule::set_attempt_limit(Origin::signed(ALICE), quiz, 1), Error::<Test>::QuizAlreadyAttempted);
+		assert_ok!(QuizModule::set_attempt_limit(Origin::signed(ALICE), quiz, 3));
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(QuizModule::set_attempt_limit(Origin::signed(ALICE), quiz, 0));
+		assert_noop!(QuizModule::set_attempt_limit(Origin::signed(ALICE), quiz, 5), Error::<Test>::QuizAlreadyAttempted);
+	});
+}
+
+#[test]
+fn larger_quizzes_reserve_larger_deposits() {
+	new_test_ext().execute_with(|| {
+		let long: Vec<Question> =
+			questions().into_iter().map(|question| Question { statement: vec![b'x'; 60], ..question }).collect();
+		let small = QuizModule::quiz_deposit(&questions());
+		let large = QuizModule::quiz_deposit(&long);
+		assert!(large > small);
+
+		let small_quiz = add_quiz(ALICE, 0);
+		assert_ok!(create(ALICE, long, key(), 0));
+		let large_quiz = QuizModule::get_latest_quiz();
+		assert_eq!(Balances::reserved_balance(ALICE), small + large);
+
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), small_quiz));
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), large_quiz));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn rating_moves_towards_consistent_scores() {
+	new_test_ext().execute_with(|| {
+		let mut current = rating::from_points(5);
+		for _ in 0..10 {
+			let next = QuizModule::next_rating(Some(current), rating::from_points(3));
+			assert!(next <= current && next >= rating::from_points(3));
+			current = next;
+		}
+		assert_eq!(rating::to_points(current), 3);
+
+		let mut current = 0;
+		for _ in 0..10 {
+			current = QuizModule::next_rating(Some(current), rating::from_points(5));
+		}
+		assert_eq!(rating::to_points(current), 5);
+	});
+}
+
+#[test]
+fn ownership_is_checked_by_account() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert!(QuizModule::is_owner(quiz, &ALICE));
+		assert!(!QuizModule::is_owner(quiz, &BOB));
+		assert!(!QuizModule::is_owner(quiz + 1, &ALICE));
+	});
+}
+
+#[test]
+fn charges_are_counted_in_whole_tokens() {
+	new_test_ext().execute_with(|| {
+		// a balance with 12 decimals
+		let token = 1_000_000_000_000u64;
+		TokenUnit::set(token);
+		fund(BOB, 10 * token);
+		let quiz = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::whole_tokens(1), token);
+		assert_eq!(QuizModule::quiz_terms(quiz).unwrap().cost_per_wrong_answer, token);
+
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		assert_eq!(Balances::free_balance(BOB), 8 * token);
+		assert!(has_event(QuizEvent::TokensCharged(quiz, BOB, ALICE, 2 * token)));
+	});
+}
+
+#[test]
+fn ratings_at_the_extremes_neither_wrap_nor_block_attempts() {
+	new_test_ext().execute_with(|| {
+		let unrated = add_quiz(ALICE, 0);
+		let top = add_quiz(ALICE, MaxRating::get());
+		assert_ok!(attempt(BOB, unrated, submission(0)));
+		assert_eq!(QuizModule::get_user_rating(&BOB), 0);
+
+		// a rating far above the cap is averaged down, not wrapped
+		QuizModule::set_user_rating(&CHARLIE, rating::from_points(u8::MAX), None);
+		assert_ok!(attempt(CHARLIE, top, key()));
+		assert_eq!(QuizModule::get_user_rating(&CHARLIE), 130);
+	});
+}
+
+#[test]
+fn reset_rating_is_averaged_up_again() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 500);
+		assert_ok!(QuizModule::reset_my_rating(Origin::signed(BOB)));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 0);
+
+		// a reset rating is 0 and averaged, only a player never rated is seeded with their score
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 250);
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+		assert_eq!(QuizModule::get_user_rating_scaled(CHARLIE), 500);
+	});
+}
+
+#[test]
+fn concurrent_attempts_are_capped() {
+	new_test_ext().execute_with(|| {
+		MaxConcurrentAttempts::set(1);
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_noop!(QuizModule::start_attempt(Origin::signed(CHARLIE), quiz), Error::<Test>::QuizBusy);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(QuizModule::start_attempt(Origin::signed(CHARLIE), quiz));
+	});
+}
+
+#[test]
+fn chain_extension_reads_quizzes() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 2);
+		let stored = QuizModule::get_quiz(&quiz_id(quiz)).unwrap();
+		assert_eq!(
+			QuizModule::extension_call(EXTENSION_GET_QUIZ, CHARLIE, &quiz.encode()),
+			Ok(Some(QuizModule::summarize(&quiz_id(quiz), stored)).encode())
+		);
+		assert_eq!(QuizModule::extension_call(EXTENSION_MAX_SCORE, CHARLIE, &quiz.encode()), Ok(Some(5u8).encode()));
+		assert_eq!(QuizModule::extension_call(EXTENSION_MAX_SCORE, CHARLIE, &(quiz + 1).encode()), Ok(None::<u8>.encode()));
+
+		// the whole input has to decode
+		let mut trailing = quiz.encode();
+		trailing.push(0);
+		assert_eq!(
+			QuizModule::extension_call(EXTENSION_GET_QUIZ, CHARLIE, &trailing),
+			Err(Error::<Test>::DecodeFailed.into())
+		);
+		assert_eq!(QuizModule::extension_call(0, CHARLIE, &[]), Err(Error::<Test>::UnknownExtensionFunction.into()));
+		assert!(QuizModule::extension_weight(EXTENSION_GET_QUIZ).is_some());
+		assert_eq!(QuizModule::extension_weight(0), None);
+	});
+}
+
+#[test]
+fn contracts_only_attempt_quizzes_when_allowed() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let input = (quiz, submission(3)).encode();
+		assert_eq!(
+			QuizModule::extension_call(EXTENSION_ATTEMPT, BOB, &input),
+			Err(Error::<Test>::ContractAttemptsDisabled.into())
+		);
+		assert!(QuizModule::get_attempt_result(&quiz_id(quiz), &BOB).is_none());
+
+		AllowContractAttempts::set(true);
+		let receipt = AttemptReceipt { score: 3, max_score: 5, charged: 2 * WRONG_ANSWER, new_rating: 3 };
+		assert_eq!(QuizModule::extension_call(EXTENSION_ATTEMPT, BOB, &input), Ok(receipt.encode()));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 2 * WRONG_ANSWER);
+		// a contract is checked like any player
+		assert_eq!(
+			QuizModule::extension_call(EXTENSION_ATTEMPT, ALICE, &input),
+			Err(Error::<Test>::OwnerCannotAttemptQuiz.into())
+		);
+		assert_eq!(QuizModule::extension_weight(EXTENSION_ATTEMPT), Some(QuizModule::attempt_quiz_weight()));
+	});
+}
+
+#[test]
+fn expired_quizzes_are_archived_when_configured() {
+	new_test_ext().execute_with(|| {
+		ArchiveExpiredQuizzes::set(true);
+		let quiz = add_quiz(ALICE, 0);
+		run_to_block(11);
+		assert!(QuizModule::get_quiz(&quiz_id(quiz)).is_none());
+		assert_eq!(QuizModule::get_archived_quiz(&quiz_id(quiz)).map(|(_, solution)| solution), Some(key()));
+
+		// governance prunes what is left once it is no longer needed
+		assert_noop!(QuizModule::prune_expired_quiz(Origin::signed(ALICE), quiz), DispatchError::BadOrigin);
+		assert_ok!(QuizModule::prune_expired_quiz(Origin::root(), quiz));
+		assert!(QuizModule::get_archived_quiz(&quiz_id(quiz)).is_none());
+		assert!(QuizModule::get_revealed_solution(&quiz_id(quiz)).is_none());
+		assert_noop!(QuizModule::prune_expired_quiz(Origin::root(), quiz), Error::<Test>::NothingToPrune);
+	});
+}
+
+#[test]
+fn expired_quizzes_are_not_archived_by_default() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		run_to_block(11);
+		assert!(QuizModule::get_archived_quiz(&quiz_id(quiz)).is_none());
+		assert_eq!(QuizModule::reveal_solution(quiz).ok(), Some(key()));
+	});
+}
+
+#[test]
+fn fast_submissions_move_the_rating_further() {
+	new_test_ext().execute_with(|| {
+		SpeedBonus::set(Perbill::from_percent(50));
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(QuizModule::start_attempt(Origin::signed(BOB), quiz));
+		assert_ok!(QuizModule::start_attempt(Origin::signed(CHARLIE), quiz));
+		assert_ok!(attempt(BOB, quiz, submission(3)));
+		System::set_block_number(6);
+		assert_ok!(attempt(CHARLIE, quiz, submission(3)));
+
+		// the score is the same, the rating is not
+		assert_eq!((score(BOB, quiz), score(CHARLIE, quiz)), (3, 3));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), 450);
+		assert_eq!(QuizModule::get_user_rating_scaled(CHARLIE), 375);
+		assert_eq!(QuizModule::rated_score(300, None), 300);
+		assert_eq!(QuizModule::rated_score(300, Some(SpeedBonusWindow::get())), 300);
+	});
+}
+
+#[test]
+fn rating_reset_costs_a_fee_and_has_a_cooldown() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		let before = Balances::free_balance(BOB);
+
+		assert_ok!(QuizModule::reset_my_rating(Origin::signed(BOB)));
+		assert_eq!(Balances::free_balance(BOB), before - RatingResetFee::get());
+		assert!(has_event(QuizEvent::RatingReset(BOB, RatingResetFee::get())));
+		assert_eq!(QuizModule::get_user_rating(&BOB), 0);
+		assert_eq!(QuizModule::get_user_attempts(BOB), 0);
+		assert!(QuizModule::get_score_history(BOB).is_empty());
+		assert_noop!(QuizModule::reset_my_rating(Origin::signed(BOB)), Error::<Test>::RatingResetTooSoon);
+
+		System::set_block_number(1 + RatingResetCooldown::get());
+		assert_ok!(QuizModule::reset_my_rating(Origin::signed(BOB)));
+	});
+}
+
+#[test]
+fn texts_need_the_minimum_length() {
+	new_test_ext().execute_with(|| {
+		let min = MinTextLen::get() as usize;
+		let shortest: Vec<Question> =
+			questions().into_iter().map(|question| Question { option4: vec![b'x'; min], ..question }).collect();
+		assert_ok!(create(ALICE, shortest, key(), 0));
+		let too_short: Vec<Question> =
+			questions().into_iter().map(|question| Question { statement: vec![b'x'; min - 1], ..question }).collect();
+		assert_noop!(create(ALICE, too_short, key(), 0), Error::<Test>::TextTooShort);
+	});
+}
+
+#[test]
+fn answers_and_solutions_have_a_bounded_encoding() {
+	assert_eq!(Answer::max_encoded_len(), 2);
+	assert_eq!(Solution::max_encoded_len(), 2 * QUESTIONS_PER_QUIZ);
+	let widest = Solution::from_answers([Answer::Several(0b1111); QUESTIONS_PER_QUIZ]);
+	assert!(widest.encode().len() <= Solution::max_encoded_len());
+}
+
+#[test]
+fn quiz_counter_follows_creation_and_removal() {
+	new_test_ext().execute_with(|| {
+		let first = add_quiz(ALICE, 0);
+		add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::get_active_quizzes(), 2);
+		assert_ok!(QuizModule::delete_quiz(Origin::signed(ALICE), first));
+		assert_eq!(QuizModule::get_active_quizzes(), 1);
+		run_to_block(11);
+		assert_eq!(QuizModule::get_active_quizzes(), 0);
+		assert_eq!(QuizModule::do_try_state(), Ok(()));
+	});
+}
+
+#[test]
+fn quizzes_of_the_first_layout_are_expired_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		// quiz 1 is still stored, quiz 2 was deleted by its owner and left its solution behind
+		let key = |count: u64| Twox64Concat::hash(&BlakeTwo256::hash_of(&count).encode());
+		put_storage_value(b"QuizModule", b"Quizzes", &key(1), (ALICE, Vec::<u8>::new(), 0u8));
+		put_storage_value(b"QuizModule", b"Solutions", &key(1), [1u8; 5]);
+		put_storage_value(b"QuizModule", b"Solutions", &key(2), [1u8; 5]);
+		put_storage_value(b"QuizModule", b"QuizToDelete", &key(11), vec![BlakeTwo256::hash_of(&1u64)]);
+		QuizCnt::<Test>::put(2);
+		StorageVersion::new(0).put::<QuizModule>();
+		<QuizModule as Hooks<u64>>::on_runtime_upgrade();
+		assert_eq!(quiz_events(), vec![QuizEvent::QuizExpired(1, 1)]);
+		assert!(!have_storage_value(b"QuizModule", b"Quizzes", &key(1)));
+		assert!(!have_storage_value(b"QuizModule", b"Solutions", &key(1)));
+		assert!(!have_storage_value(b"QuizModule", b"Solutions", &key(2)));
+		assert!(!have_storage_value(b"QuizModule", b"QuizToDelete", &key(11)));
+		assert_eq!(QuizModule::get_active_quizzes(), 0);
+		assert_eq!(QuizModule::get_latest_quiz(), 2);
+		assert_eq!(StorageVersion::get::<QuizModule>(), STORAGE_VERSION);
+	});
+}
+
+#[test]
+fn rejected_content_is_not_stored() {
+	new_test_ext().execute_with(|| {
+		let mut spam = questions();
+		spam[2].statement = b"buy spam now".to_vec();
+		assert_noop!(create(ALICE, spam.clone(), key(), 0), Error::<Test>::ContentRejected);
+		let quiz = add_quiz(ALICE, 0);
+		assert_noop!(
+			QuizModule::edit_quiz(Origin::signed(ALICE), quiz, spam.clone(), key()),
+			Error::<Test>::ContentRejected
+		);
+		assert_noop!(
+			QuizModule::add_quiz_translation(Origin::signed(ALICE), quiz, *b"de", spam),
+			Error::<Test>::ContentRejected
+		);
+	});
+}
+
+#[test]
+fn feedback_marks_the_questions_answered_right() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		let answers = key().answers();
+		let submitted = with_answer(with_answer(submission(0), 0, answers[0]), 2, answers[2]);
+		assert_ok!(attempt(BOB, quiz, submitted));
+		assert_eq!(last_quiz_event(), QuizEvent::AnswerFeedback(quiz, BOB, 0b00101));
+		assert_eq!(QuizModule::correct_mask(&key(), &key()), 0b11111);
+	});
+}
+
+#[test]
+fn daily_quota_starts_over_in_the_next_window() {
+	new_test_ext().execute_with(|| {
+		DailyAttemptQuota::set(2);
+		let quiz = add_quiz(ALICE, 0);
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_ok!(attempt(BOB, quiz, key()));
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::DailyQuotaExceeded);
+		assert_ok!(attempt(CHARLIE, quiz, key()));
+
+		System::set_block_number(BlocksPerDay::get());
+		assert_ok!(attempt(BOB, quiz, key()));
+	});
+}
+
+#[test]
+fn difficulty_is_the_share_of_answers_missed() {
+	new_test_ext().execute_with(|| {
+		let hard = add_quiz(ALICE, 0);
+		let easy = add_quiz(ALICE, 0);
+		assert_eq!(QuizModule::observed_difficulty(hard), None);
+		for who in [BOB, CHARLIE] {
+			assert_ok!(attempt(who, hard, submission(1)));
+			assert_ok!(attempt(who, easy, submission(4)));
+		}
+		assert_eq!(QuizModule::observed_difficulty(hard), Some(Perbill::from_percent(80)));
+		assert_eq!(QuizModule::observed_difficulty(easy), Some(Perbill::from_percent(20)));
+	});
+}
+
+#[test]
+fn attempts_that_would_reap_the_player_are_refused() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(10);
+		let quiz = add_quiz(ALICE, 0);
+		fund(POOR, 25);
+		assert_noop!(attempt(POOR, quiz, submission(3)), Error::<Test>::WouldReapAccount);
+		assert_eq!(QuizModule::dry_run_attempt(quiz, &POOR, submission(3), None), Err(AttemptStatus::WouldReapAccount));
+		// paying less leaves the existential deposit
+		assert_ok!(attempt(POOR, quiz, submission(4)));
+		assert_eq!(Balances::free_balance(POOR), 15);
+	});
+}
+
+#[test]
+fn quizzes_are_fetched_in_the_order_asked_for() {
+	new_test_ext().execute_with(|| {
+		let first = add_quiz(ALICE, 1);
+		let second = add_quiz(ALICE, 2);
+		let fetched = QuizModule::get_quizzes(vec![second, 42, first]);
+		assert_eq!(
+			fetched.iter().map(|quiz| quiz.as_ref().map(|quiz| quiz.count)).collect::<Vec<_>>(),
+			vec![Some(second), None, Some(first)]
+		);
+	});
+}
+
+#[test]
+fn banned_accounts_neither_create_nor_attempt() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		assert_noop!(QuizModule::ban_account(Origin::signed(ALICE), BOB), DispatchError::BadOrigin);
+		assert_ok!(QuizModule::ban_account(Origin::root(), BOB));
+		assert_noop!(create(BOB, questions(), key(), 0), Error::<Test>::AccountBanned);
+		assert_noop!(attempt(BOB, quiz, key()), Error::<Test>::AccountBanned);
+		assert_noop!(QuizModule::start_attempt(Origin::signed(BOB), quiz), Error::<Test>::AccountBanned);
+
+		assert_ok!(QuizModule::unban_account(Origin::root(), BOB));
+		assert_ok!(create(BOB, questions(), key(), 0));
+		assert_ok!(attempt(BOB, quiz, key()));
+	});
+}
+
+#[test]
+fn rating_update_starts_from_the_rating_before_the_attempt() {
+	new_test_ext().execute_with(|| {
+		let quiz = add_quiz(ALICE, 0);
+		QuizModule::set_user_rating(&BOB, rating::from_points(4), None);
+		assert_eq!(QuizModule::get_rating_count(4), 1);
+
+		// halfway from 4 to 2 with the mock's alpha of one half
+		assert_ok!(QuizModule::attempt_quiz_with_receipt(Origin::signed(BOB), quiz, submission(2), None));
+		assert_eq!(QuizModule::get_user_rating_scaled(BOB), rating::from_points(3));
+		assert_eq!((QuizModule::get_rating_count(4), QuizModule::get_rating_count(3)), (0, 1));
+		let receipt = AttemptReceipt { score: 2, max_score: 5, charged: 3 * WRONG_ANSWER, new_rating: 3 };
+		assert_eq!(last_quiz_event(), QuizEvent::AttemptReceiptIssued(quiz, BOB, receipt));
+	});
+}
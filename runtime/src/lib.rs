@@ -33,7 +33,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -60,6 +60,9 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// One whole token, balances are kept with 12 decimals.
+pub const UNIT: Balance = 1_000_000_000_000;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -102,7 +105,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 104,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -279,7 +282,55 @@ impl pallet_sudo::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TokensPerQuestion : u32 = 100000000;
+	pub const TokensPerQuestion : Balance = 1;
+	// `TokensPerQuestion` is counted in whole tokens, a wrong answer costs one
+	pub const TokenUnit : Balance = UNIT;
+	pub const QuizDeposit : Balance = 1000000000;
+	pub const QuizDepositPerByte : Balance = 1000000;
+	pub const SlashReportThreshold : u32 = 3;
+	pub const MaxBulkDelete : u32 = 50;
+	pub const MaxQuizBatch : u32 = 10;
+	pub const MaxRefundsPerCall : u32 = 50;
+	pub const MaxActiveQuizzes : u32 = 10000;
+	pub WrongAnswerPenalty : Perbill = Perbill::zero();
+	pub OrdinalPartialCredit : Perbill = Perbill::from_percent(50);
+	pub MultiSelectPartialCredit : Perbill = Perbill::zero();
+	pub const ShuffleOptions : bool = false;
+	pub const AttemptFee : Balance = 0;
+	pub const MaxTextLen : u32 = 1024;
+	pub const MinTextLen : u32 = 1;
+	pub const MaxRating : u8 = 5;
+	pub const StretchMargin : u8 = 1;
+	pub const StretchMultiplier : u32 = 2;
+	pub const TokenCostRounding : pallet_template::CostRounding = pallet_template::CostRounding::Down;
+	pub const EarningsLockPeriod : BlockNumber = 100;
+	pub ReferralCut : Perbill = Perbill::from_percent(10);
+	pub const QuizPalletId : PalletId = PalletId(*b"py/quizm");
+	pub const TournamentWinners : u32 = 3;
+	pub const AttemptCooldown : BlockNumber = 0;
+	pub const ResetCooldownOnImprovement : bool = false;
+	pub const MinAttemptsForRating : u32 = 3;
+	pub const RatingMilestoneStep : u8 = 1;
+	pub RatingAlpha : Perbill = Perbill::from_rational(1u32, 6u32);
+	pub RatingCostFactor : Perbill = Perbill::zero();
+	pub const RejectUniformSolutions : bool = false;
+	pub const MaxWrongAttempts : u32 = 5;
+	pub const StrikeScoreThreshold : u8 = 2;
+	pub const MaxDeletionBucketLen : u32 = 256;
+	pub ExpiryWeightBudget : Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+	pub const ExpiryGracePeriod : BlockNumber = 5;
+	pub const MaxConcurrentAttempts : u32 = 0;
+	// the runtime has no contracts pallet to call `extension_call` from
+	pub const AllowContractAttempts : bool = false;
+	pub const ArchiveExpiredQuizzes : bool = false;
+	pub SpeedBonus : Perbill = Perbill::zero();
+	pub const SpeedBonusWindow : BlockNumber = 10;
+	pub const RatingResetFee : Balance = 1000000000;
+	pub const RatingResetCooldown : BlockNumber = 14400;
+	pub const DailyAttemptQuota : u32 = 0;
+	pub const BlocksPerDay : BlockNumber = 14400;
+	pub const VestingThreshold : Balance = 1000000000000000;
+	pub const VestingPeriod : BlockNumber = 14400 * 30;
 }
 
 /// Configure the pallet-template in pallets/template.
@@ -287,6 +338,63 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type TokensPerQuestion = TokensPerQuestion;
+	type TokenUnit = TokenUnit;
+	type QuizDeposit = QuizDeposit;
+	type QuizDepositPerByte = QuizDepositPerByte;
+	type SlashReportThreshold = SlashReportThreshold;
+	// no treasury in this runtime, slashed deposits are burned
+	type Slashed = ();
+	type MaxBulkDelete = MaxBulkDelete;
+	type MaxQuizBatch = MaxQuizBatch;
+	type MaxRefundsPerCall = MaxRefundsPerCall;
+	type MaxActiveQuizzes = MaxActiveQuizzes;
+	type GovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+	type CreateOrigin = frame_system::EnsureSigned<AccountId>;
+	type AttemptFee = AttemptFee;
+	// no treasury in this runtime, attempt fees are burned
+	type AttemptFeeDestination = ();
+	type WrongAnswerPenalty = WrongAnswerPenalty;
+	type OrdinalPartialCredit = OrdinalPartialCredit;
+	type MultiSelectPartialCredit = MultiSelectPartialCredit;
+	type ShuffleOptions = ShuffleOptions;
+	type MaxTextLen = MaxTextLen;
+	type MinTextLen = MinTextLen;
+	type MaxRating = MaxRating;
+	type StretchMargin = StretchMargin;
+	type StretchMultiplier = StretchMultiplier;
+	type TokenCostRounding = TokenCostRounding;
+	type EarningsLockPeriod = EarningsLockPeriod;
+	type ReferralCut = ReferralCut;
+	type PalletId = QuizPalletId;
+	type TournamentWinners = TournamentWinners;
+	type AttemptCooldown = AttemptCooldown;
+	type ResetCooldownOnImprovement = ResetCooldownOnImprovement;
+	type MinAttemptsForRating = MinAttemptsForRating;
+	type RatingMilestoneStep = RatingMilestoneStep;
+	type RatingAlpha = RatingAlpha;
+	type RatingCostFactor = RatingCostFactor;
+	type MaxDeletionBucketLen = MaxDeletionBucketLen;
+	type ExpiryWeightBudget = ExpiryWeightBudget;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type MaxConcurrentAttempts = MaxConcurrentAttempts;
+	type AllowContractAttempts = AllowContractAttempts;
+	type ArchiveExpiredQuizzes = ArchiveExpiredQuizzes;
+	type SpeedBonus = SpeedBonus;
+	type SpeedBonusWindow = SpeedBonusWindow;
+	type RatingResetFee = RatingResetFee;
+	type RatingResetCooldown = RatingResetCooldown;
+	type DailyAttemptQuota = DailyAttemptQuota;
+	type BlocksPerDay = BlocksPerDay;
+	type VestingThreshold = VestingThreshold;
+	type VestingPeriod = VestingPeriod;
+	type RejectUniformSolutions = RejectUniformSolutions;
+	type MaxWrongAttempts = MaxWrongAttempts;
+	type StrikeScoreThreshold = StrikeScoreThreshold;
+	type OnQuizCompleted = ();
+	type OnPerfectScore = ();
+	type OnQuizProposed = ();
+	// no vesting pallet in this runtime, every prize is paid liquid
+	type Vesting = pallet_template::NoVesting<Balances, BlockNumber>;
 }
 pub use frame_system::EnsureSigned;
 parameter_types! {
@@ -481,6 +589,76 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_template_runtime_api::QuizApi<
+		Block,
+		AccountId,
+		BlockNumber,
+		Hash,
+		pallet_template::Solution,
+		pallet_template::QuizSummary<Runtime>,
+		pallet_template::AttemptStatus,
+		pallet_template::QuizTerms<Runtime>,
+		Perbill,
+		pallet_template::Quiz<Runtime>,
+	> for Runtime {
+		fn deletion_block(quiz_count: u64) -> Option<BlockNumber> {
+			QuizModule::deletion_block(quiz_count)
+		}
+
+		fn scheduled_deletions(block: BlockNumber) -> Vec<Hash> {
+			QuizModule::scheduled_deletions(block)
+		}
+
+		fn attempt_count(quiz_count: u64) -> u32 {
+			QuizModule::attempt_count(quiz_count)
+		}
+
+		fn reveal_solution(quiz_count: u64) -> Option<pallet_template::Solution> {
+			QuizModule::reveal_solution(quiz_count).ok()
+		}
+
+		fn option_order(quiz_count: u64, who: AccountId) -> Option<Vec<[u8; 4]>> {
+			QuizModule::option_order(quiz_count, &who)
+		}
+
+		fn list_quizzes(
+			start_after: Option<u64>,
+			limit: u32,
+		) -> (Vec<(u64, pallet_template::QuizSummary<Runtime>)>, Option<u64>) {
+			QuizModule::list_quizzes(start_after, limit)
+		}
+
+		fn explanations(quiz_count: u64, who: AccountId) -> Option<Vec<Vec<u8>>> {
+			QuizModule::explanations_for(quiz_count, &who)
+				.map(|explanations| explanations.into_iter().map(|explanation| explanation.into_inner()).collect())
+		}
+
+		fn dry_run_attempt(
+			quiz_count: u64,
+			who: AccountId,
+			submission: pallet_template::Solution,
+			referrer: Option<AccountId>,
+		) -> Result<u8, pallet_template::AttemptStatus> {
+			QuizModule::dry_run_attempt(quiz_count, &who, submission, referrer)
+		}
+
+		fn quiz_terms(quiz_count: u64) -> Option<pallet_template::QuizTerms<Runtime>> {
+			QuizModule::quiz_terms(quiz_count)
+		}
+
+		fn user_rank(who: AccountId) -> Option<u32> {
+			QuizModule::user_rank(&who)
+		}
+
+		fn observed_difficulty(quiz_count: u64) -> Option<Perbill> {
+			QuizModule::observed_difficulty(quiz_count)
+		}
+
+		fn get_quizzes(quiz_counts: Vec<u64>) -> Vec<Option<pallet_template::Quiz<Runtime>>> {
+			QuizModule::get_quizzes(quiz_counts)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,